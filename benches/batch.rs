@@ -0,0 +1,66 @@
+//!Compares batched `xxh3_64` against hashing keys one by one.
+//!
+//!Run with `cargo bench --features xxh3 --bench batch`
+
+use core::hint::black_box;
+use std::time::{Duration, Instant};
+
+use xxhash_rust::xxh3::{xxh3_64, xxh3_64_batch, xxh3_64_u64_batch};
+
+const KEYS: usize = 1 << 16;
+const ROUNDS: usize = 50;
+
+//Returns fastest of several rounds to reduce noise
+fn measure(mut run: impl FnMut()) -> Duration {
+    (0..ROUNDS).map(|_| {
+        let start = Instant::now();
+        run();
+        start.elapsed()
+    }).min().unwrap()
+}
+
+fn report(name: &str, scalar: Duration, batch: Duration) {
+    println!("{:<12} scalar {:>8.2} ns/key, batch {:>8.2} ns/key, speedup {:.2}x",
+             name,
+             scalar.as_nanos() as f64 / KEYS as f64,
+             batch.as_nanos() as f64 / KEYS as f64,
+             scalar.as_nanos() as f64 / batch.as_nanos() as f64);
+}
+
+fn main() {
+    let data: Vec<u8> = (0..KEYS * 128).map(|idx| (idx as u64).wrapping_mul(0x9E3779B97F4A7C15).rotate_left(17) as u8).collect();
+    let mut out = vec![0u64; KEYS];
+
+    for (min_len, max_len) in [(4, 8), (8, 8), (9, 16), (16, 16), (17, 32), (9, 32), (8, 32), (33, 128)] {
+        let keys: Vec<&[u8]> = (0..KEYS).map(|idx| {
+            //Lengths are scattered, as periodic pattern would be learned by branch predictor
+            let len = min_len + ((idx as u64).wrapping_mul(0x9E3779B97F4A7C15) >> 32) as usize % (max_len - min_len + 1);
+            &data[idx * max_len..idx * max_len + len]
+        }).collect();
+
+        let scalar = measure(|| {
+            for (key, out) in keys.iter().zip(out.iter_mut()) {
+                *out = xxh3_64(black_box(key));
+            }
+            black_box(&out);
+        });
+        let batch = measure(|| {
+            xxh3_64_batch(black_box(&keys), &mut out);
+            black_box(&out);
+        });
+        report(&format!("{}..={}", min_len, max_len), scalar, batch);
+    }
+
+    let keys: Vec<u64> = (0..KEYS as u64).map(|idx| idx.wrapping_mul(0x9E3779B97F4A7C15)).collect();
+    let scalar = measure(|| {
+        for (key, out) in keys.iter().zip(out.iter_mut()) {
+            *out = xxh3_64(&black_box(*key).to_le_bytes());
+        }
+        black_box(&out);
+    });
+    let batch = measure(|| {
+        xxh3_64_u64_batch(black_box(&keys), &mut out);
+        black_box(&out);
+    });
+    report("u64", scalar, batch);
+}
@@ -0,0 +1,247 @@
+//!`xxhsum` compatible command line utility
+//!
+//!Prints or checks xxHash checksums, producing the same output as reference `xxhsum`.
+
+use std::ffi::OsString;
+use std::io::{self, Read, Write};
+use std::{env, fs, process};
+
+use xxhash_rust::checksum::{Algorithm, ChecksumLine, Format, LineStatus, Verifier};
+
+const STDIN_NAME: &str = "-";
+
+fn algorithm_from_flag(flag: &str) -> Option<Algorithm> {
+    match flag {
+        "0" | "32" => Some(Algorithm::Xxh32),
+        "1" | "64" => Some(Algorithm::Xxh64),
+        "2" | "128" => Some(Algorithm::Xxh3_128),
+        "3" => Some(Algorithm::Xxh3_64),
+        _ => None,
+    }
+}
+
+struct Args {
+    algorithm: Algorithm,
+    is_bsd: bool,
+    is_check: bool,
+    is_quiet: bool,
+    is_status: bool,
+    is_strict: bool,
+    is_warn: bool,
+    files: Vec<OsString>,
+}
+
+fn usage(exe: &str) -> String {
+    format!("Usage: {} [OPTION] [FILE]...
+Print or check xxHash checksums.
+With no FILE, or when FILE is -, read standard input.
+
+Options:
+  -H#          select an xxhash algorithm (default: 1)
+               0: XXH32
+               1: XXH64
+               2: XXH128 (also called XXH3_128bits)
+               3: XXH3 (also called XXH3_64bits)
+  -c, --check  read xxHash checksums from FILEs and check them
+      --tag    produce BSD-style checksum lines
+  -h, --help   display this help and exit
+  -V, --version
+               output version information and exit
+
+The following options are useful only when verifying checksums (-c):
+  -q, --quiet  don't print OK for each successfully verified file
+      --status don't output anything, status code shows success
+      --strict exit non-zero for improperly formatted checksum lines
+  -w, --warn   warn about improperly formatted checksum lines
+", exe)
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = env::args_os();
+    let exe = args.next().map(|exe| exe.to_string_lossy().into_owned()).unwrap_or_else(|| "xxhsum".to_owned());
+
+    let mut result = Args {
+        algorithm: Algorithm::Xxh64,
+        is_bsd: false,
+        is_check: false,
+        is_quiet: false,
+        is_status: false,
+        is_strict: false,
+        is_warn: false,
+        files: Vec::new(),
+    };
+
+    let mut is_options_end = false;
+    for arg in args {
+        if is_options_end {
+            result.files.push(arg);
+            continue;
+        }
+
+        let flag = match arg.to_str() {
+            Some(flag) if flag.starts_with('-') && flag != STDIN_NAME => flag,
+            _ => {
+                result.files.push(arg);
+                continue;
+            }
+        };
+
+        match flag {
+            "--" => is_options_end = true,
+            "-c" | "--check" => result.is_check = true,
+            "--tag" => result.is_bsd = true,
+            "-q" | "--quiet" => result.is_quiet = true,
+            "--status" => result.is_status = true,
+            "--strict" => result.is_strict = true,
+            "-w" | "--warn" => result.is_warn = true,
+            "-h" | "--help" => {
+                print!("{}", usage(&exe));
+                process::exit(0);
+            },
+            "-V" | "--version" => {
+                println!("{} {} (xxhash-rust)", exe, env!("CARGO_PKG_VERSION"));
+                process::exit(0);
+            },
+            flag => match flag.strip_prefix("-H").and_then(algorithm_from_flag) {
+                Some(algorithm) => result.algorithm = algorithm,
+                None => return Err(format!("{}: unrecognized option '{}'\n{}", exe, flag, usage(&exe))),
+            },
+        }
+    }
+
+    if result.files.is_empty() {
+        result.files.push(OsString::from(STDIN_NAME));
+    }
+
+    Ok(result)
+}
+
+fn open(file_name: &OsString) -> io::Result<Box<dyn Read>> {
+    if file_name == STDIN_NAME {
+        Ok(Box::new(io::stdin()))
+    } else {
+        Ok(Box::new(fs::File::open(file_name)?))
+    }
+}
+
+fn display_name(file_name: &OsString) -> OsString {
+    if file_name == STDIN_NAME {
+        OsString::from("stdin")
+    } else {
+        file_name.clone()
+    }
+}
+
+//Strips OS error code from error description
+fn error_description(error: &io::Error) -> String {
+    let description = error.to_string();
+    match description.find(" (os error") {
+        Some(end) => description[..end].to_owned(),
+        None => description,
+    }
+}
+
+fn hash_files(args: &Args, out: &mut impl Write) -> io::Result<bool> {
+    let mut is_ok = true;
+
+    for file_name in args.files.iter() {
+        let display_name = display_name(file_name);
+        match open(file_name).and_then(|file| args.algorithm.hash_reader(file)) {
+            Ok(hash) => {
+                let format = if args.is_bsd { Format::Bsd } else { Format::Gnu };
+                ChecksumLine::new(args.algorithm, hash, display_name).with_format(format).write_to(&mut *out)?;
+                out.write_all(b"\n")?;
+            },
+            Err(error) => {
+                out.flush()?;
+                eprintln!("Error: Could not open '{}': {}.", display_name.to_string_lossy(), error_description(&error));
+                is_ok = false;
+            }
+        }
+    }
+
+    Ok(is_ok)
+}
+
+fn check_file(args: &Args, file_name: &OsString, out: &mut impl Write) -> io::Result<bool> {
+    let display_name = display_name(file_name);
+    let display_name = display_name.to_string_lossy();
+    let input = match open(file_name) {
+        Ok(input) => io::BufReader::new(input),
+        Err(error) => {
+            out.flush()?;
+            eprintln!("Error: Could not open '{}': {}.", display_name, error_description(&error));
+            return Ok(false);
+        }
+    };
+
+    let mut verifier = Verifier::new(input);
+    for result in &mut verifier {
+        let result = result?;
+        match result.status {
+            LineStatus::Ok(line) => if !(args.is_status || args.is_quiet) {
+                line.write_status(true, &mut *out)?;
+                out.write_all(b"\n")?;
+            },
+            LineStatus::Failed(line) => if !args.is_status {
+                line.write_status(false, &mut *out)?;
+                out.write_all(b"\n")?;
+            },
+            LineStatus::ReadFailure(line, error) => {
+                out.flush()?;
+                eprintln!("{}:{}: Could not open or read '{}': {}.", display_name, result.line_number, line.file_name.to_string_lossy(), error_description(&error));
+            },
+            LineStatus::ImproperlyFormatted => if args.is_warn {
+                out.flush()?;
+                eprintln!("{}:{}: Error: Improperly formatted checksum line.", display_name, result.line_number);
+            },
+        }
+    }
+
+    let report = verifier.report();
+    if report.properly_formatted == 0 {
+        out.flush()?;
+        eprintln!("{}: no properly formatted xxHash checksum lines found", display_name);
+    } else if !args.is_status {
+        write!(out, "{}", report)?;
+    }
+
+    Ok(report.is_success(args.is_strict))
+}
+
+fn run(args: &Args) -> io::Result<bool> {
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+
+    let is_ok = if args.is_check {
+        let mut is_ok = true;
+        for file_name in args.files.iter() {
+            is_ok &= check_file(args, file_name, &mut out)?;
+        }
+        is_ok
+    } else {
+        hash_files(args, &mut out)?
+    };
+
+    out.flush()?;
+    Ok(is_ok)
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(error) => {
+            eprint!("{}", error);
+            process::exit(1);
+        }
+    };
+
+    match run(&args) {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            process::exit(1);
+        }
+    }
+}
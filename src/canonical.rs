@@ -0,0 +1,291 @@
+//!Canonical representation of hashes
+//!
+//!Canonical representation is big-endian byte sequence of hash, identical on all platforms,
+//!that matches `XXH32_canonical_t`, `XXH64_canonical_t` and `XXH128_canonical_t` of reference implementation.
+//!
+//!It is recommended to use it when persisting hashes or exchanging them between machines.
+
+use core::{fmt, str};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///Error parsing canonical hash from hex string.
+pub enum ParseCanonicalError {
+    ///Input string has unexpected length.
+    InvalidLength,
+    ///Input string contains non hex digit.
+    InvalidDigit,
+}
+
+impl fmt::Display for ParseCanonicalError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength => fmt.write_str("invalid canonical hash length"),
+            Self::InvalidDigit => fmt.write_str("invalid hex digit in canonical hash"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseCanonicalError {}
+
+#[inline(always)]
+const fn hex_digit_value(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn parse_hex(input: &str, out: &mut [u8]) -> Result<(), ParseCanonicalError> {
+    let input = input.as_bytes();
+    if input.len() != out.len() * 2 {
+        return Err(ParseCanonicalError::InvalidLength);
+    }
+
+    for (byte, digits) in out.iter_mut().zip(input.chunks_exact(2)) {
+        match (hex_digit_value(digits[0]), hex_digit_value(digits[1])) {
+            (Some(high), Some(low)) => *byte = (high << 4) | low,
+            _ => return Err(ParseCanonicalError::InvalidDigit),
+        }
+    }
+
+    Ok(())
+}
+
+macro_rules! define_canonical {
+    ($(#[$meta:meta])* $name:ident($hash:ty)) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(transparent)]
+        pub struct $name([u8; core::mem::size_of::<$hash>()]);
+
+        impl $name {
+            #[inline(always)]
+            ///Creates canonical representation of provided hash.
+            pub const fn from_hash(hash: $hash) -> Self {
+                Self(hash.to_be_bytes())
+            }
+
+            #[inline(always)]
+            ///Creates canonical representation from its raw big-endian bytes.
+            pub const fn from_bytes(bytes: [u8; core::mem::size_of::<$hash>()]) -> Self {
+                Self(bytes)
+            }
+
+            #[inline(always)]
+            ///Restores hash from canonical representation.
+            pub const fn to_hash(&self) -> $hash {
+                <$hash>::from_be_bytes(self.0)
+            }
+
+            #[inline(always)]
+            ///Returns raw big-endian bytes.
+            pub const fn to_bytes(&self) -> [u8; core::mem::size_of::<$hash>()] {
+                self.0
+            }
+        }
+
+        impl From<$hash> for $name {
+            #[inline(always)]
+            fn from(hash: $hash) -> Self {
+                Self::from_hash(hash)
+            }
+        }
+
+        impl From<$name> for $hash {
+            #[inline(always)]
+            fn from(canonical: $name) -> Self {
+                canonical.to_hash()
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            #[inline(always)]
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            #[inline]
+            fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+                for byte in self.0.iter() {
+                    write!(fmt, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl str::FromStr for $name {
+            type Err = ParseCanonicalError;
+
+            #[inline]
+            fn from_str(input: &str) -> Result<Self, Self::Err> {
+                let mut result = [0; core::mem::size_of::<$hash>()];
+                parse_hex(input, &mut result)?;
+                Ok(Self(result))
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $name {
+            ///Serializes as hex string in human readable formats, otherwise as raw bytes.
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    serializer.collect_str(self)
+                } else {
+                    serializer.serialize_bytes(&self.0)
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                use serde::de::Error;
+
+                struct HexVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for HexVisitor {
+                    type Value = $name;
+
+                    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        write!(fmt, "{} hex digits", 2 * core::mem::size_of::<$hash>())
+                    }
+
+                    fn visit_str<E: Error>(self, input: &str) -> Result<Self::Value, E> {
+                        input.parse().map_err(E::custom)
+                    }
+                }
+
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_str(HexVisitor)
+                } else {
+                    let bytes = crate::serde_utils::ByteBuf::<{ core::mem::size_of::<$hash>() }>::deserialize(deserializer)?;
+                    let mut result = [0; core::mem::size_of::<$hash>()];
+                    if bytes.as_slice().len() != result.len() {
+                        return Err(D::Error::invalid_length(bytes.as_slice().len(), &"canonical hash bytes"));
+                    }
+                    result.copy_from_slice(bytes.as_slice());
+                    Ok(Self(result))
+                }
+            }
+        }
+    };
+}
+
+define_canonical!(
+    ///Canonical representation of 32bit hash, equivalent of `XXH32_canonical_t`
+    Xxh32Canonical(u32)
+);
+define_canonical!(
+    ///Canonical representation of 64bit hash, equivalent of `XXH64_canonical_t`
+    Xxh64Canonical(u64)
+);
+define_canonical!(
+    ///Canonical representation of 128bit hash, equivalent of `XXH128_canonical_t`
+    Xxh128Canonical(u128)
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///128bit hash split into its halves, equivalent of `XXH128_hash_t`
+///
+///Ordering follows `XXH128_cmp`: `high64` is compared first, then `low64`.
+pub struct Xxh128Hash {
+    low64: u64,
+    high64: u64,
+}
+
+impl Xxh128Hash {
+    #[inline(always)]
+    ///Creates hash from its halves.
+    pub const fn new(low64: u64, high64: u64) -> Self {
+        Self {
+            low64,
+            high64,
+        }
+    }
+
+    #[inline(always)]
+    ///Creates hash from `u128` as returned by `xxh3_128`.
+    pub const fn from_u128(hash: u128) -> Self {
+        Self::new(hash as u64, (hash >> 64) as u64)
+    }
+
+    #[inline(always)]
+    ///Returns hash as `u128`.
+    pub const fn to_u128(&self) -> u128 {
+        ((self.high64 as u128) << 64) | (self.low64 as u128)
+    }
+
+    #[inline(always)]
+    ///Returns lower 64 bits of hash.
+    pub const fn low64(&self) -> u64 {
+        self.low64
+    }
+
+    #[inline(always)]
+    ///Returns upper 64 bits of hash.
+    pub const fn high64(&self) -> u64 {
+        self.high64
+    }
+
+    #[inline(always)]
+    ///Returns canonical representation of hash.
+    pub const fn to_canonical(&self) -> Xxh128Canonical {
+        Xxh128Canonical::from_hash(self.to_u128())
+    }
+
+    #[inline(always)]
+    ///Restores hash from its canonical representation.
+    pub const fn from_canonical(canonical: &Xxh128Canonical) -> Self {
+        Self::from_u128(canonical.to_hash())
+    }
+}
+
+impl PartialOrd for Xxh128Hash {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Xxh128Hash {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.high64.cmp(&other.high64).then(self.low64.cmp(&other.low64))
+    }
+}
+
+impl From<u128> for Xxh128Hash {
+    #[inline(always)]
+    fn from(hash: u128) -> Self {
+        Self::from_u128(hash)
+    }
+}
+
+impl From<Xxh128Hash> for u128 {
+    #[inline(always)]
+    fn from(hash: Xxh128Hash) -> Self {
+        hash.to_u128()
+    }
+}
+
+impl From<Xxh128Canonical> for Xxh128Hash {
+    #[inline(always)]
+    fn from(canonical: Xxh128Canonical) -> Self {
+        Self::from_canonical(&canonical)
+    }
+}
+
+impl From<Xxh128Hash> for Xxh128Canonical {
+    #[inline(always)]
+    fn from(hash: Xxh128Hash) -> Self {
+        hash.to_canonical()
+    }
+}
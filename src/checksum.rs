@@ -0,0 +1,488 @@
+//!Checksum files in `xxhsum` format
+//!
+//!Supports both GNU (`<hex>  <path>`) and BSD (`XXH3 (<path>) = <hex>`) styles,
+//!including escaped file names, as produced by reference `xxhsum`.
+//!
+//!```rust
+//!use xxhash_rust::checksum::{Algorithm, ChecksumLine, Format};
+//!
+//!let line: ChecksumLine = "XXH3_9555e8555c62dcfd  a.txt".parse().unwrap();
+//!assert_eq!(line.algorithm, Algorithm::Xxh3_64);
+//!assert_eq!(line.hash, Algorithm::Xxh3_64.hash_reader(&b"hello"[..]).unwrap());
+//!assert_eq!(line.with_format(Format::Bsd).to_string(), "XXH3 (a.txt) = 9555e8555c62dcfd");
+//!```
+
+use std::borrow::{Cow, ToOwned};
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::str::FromStr;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::canonical::{Xxh32Canonical, Xxh64Canonical, Xxh128Canonical};
+use crate::{xxh3, xxh32, xxh64};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///Hash algorithm of checksum line.
+pub enum Algorithm {
+    ///`xxh32` with seed `0`, selected by `-H0`
+    Xxh32,
+    ///`xxh64` with seed `0`, selected by `-H1`
+    Xxh64,
+    ///`xxh3_128` with default secret, selected by `-H2`
+    Xxh3_128,
+    ///`xxh3_64` with default secret, selected by `-H3`
+    Xxh3_64,
+}
+
+impl Algorithm {
+    ///Returns name used in BSD style lines.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Algorithm::Xxh32 => "XXH32",
+            Algorithm::Xxh64 => "XXH64",
+            Algorithm::Xxh3_128 => "XXH128",
+            Algorithm::Xxh3_64 => "XXH3",
+        }
+    }
+
+    ///Looks up algorithm by name used in BSD style lines.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "XXH32" => Some(Algorithm::Xxh32),
+            "XXH64" => Some(Algorithm::Xxh64),
+            "XXH128" => Some(Algorithm::Xxh3_128),
+            "XXH3" => Some(Algorithm::Xxh3_64),
+            _ => None,
+        }
+    }
+
+    ///Returns size of hash in bytes.
+    pub const fn hash_size(self) -> usize {
+        match self {
+            Algorithm::Xxh32 => 4,
+            Algorithm::Xxh64 | Algorithm::Xxh3_64 => 8,
+            Algorithm::Xxh3_128 => 16,
+        }
+    }
+
+    ///Hashes content of `input` until its end.
+    pub fn hash_reader<R: Read>(self, input: R) -> io::Result<u128> {
+        match self {
+            Algorithm::Xxh32 => xxh32::hash_reader(input, 0).map(u128::from),
+            Algorithm::Xxh64 => xxh64::hash_reader(input, 0).map(u128::from),
+            Algorithm::Xxh3_128 => xxh3::hash_reader128(input),
+            Algorithm::Xxh3_64 => xxh3::hash_reader(input).map(u128::from),
+        }
+    }
+
+    fn parse_hash(self, hash: &str) -> Option<u128> {
+        match self {
+            Algorithm::Xxh32 => hash.parse::<Xxh32Canonical>().ok().map(|hash| hash.to_hash() as u128),
+            Algorithm::Xxh64 | Algorithm::Xxh3_64 => hash.parse::<Xxh64Canonical>().ok().map(|hash| hash.to_hash() as u128),
+            Algorithm::Xxh3_128 => hash.parse::<Xxh128Canonical>().ok().map(|hash| hash.to_hash()),
+        }
+    }
+
+    fn fmt_hash(self, hash: u128, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Algorithm::Xxh32 => fmt::Display::fmt(&Xxh32Canonical::from_hash(hash as u32), fmt),
+            Algorithm::Xxh64 | Algorithm::Xxh3_64 => fmt::Display::fmt(&Xxh64Canonical::from_hash(hash as u64), fmt),
+            Algorithm::Xxh3_128 => fmt::Display::fmt(&Xxh128Canonical::from_hash(hash), fmt),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///Style of checksum line.
+pub enum Format {
+    ///`<hex>  <path>`, with `XXH3_` prefix for `xxh3_64`
+    Gnu,
+    ///`<ALGORITHM> (<path>) = <hex>`, produced by `xxhsum --tag`
+    Bsd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///Error parsing improperly formatted checksum line.
+pub struct ParseChecksumError;
+
+impl fmt::Display for ParseChecksumError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("improperly formatted checksum line")
+    }
+}
+
+impl std::error::Error for ParseChecksumError {}
+
+#[cfg(unix)]
+fn file_name_from_bytes(file_name: Vec<u8>) -> OsString {
+    std::os::unix::ffi::OsStringExt::from_vec(file_name)
+}
+
+#[cfg(not(unix))]
+fn file_name_from_bytes(file_name: Vec<u8>) -> OsString {
+    match String::from_utf8(file_name) {
+        Ok(file_name) => file_name.into(),
+        Err(error) => String::from_utf8_lossy(error.as_bytes()).into_owned().into(),
+    }
+}
+
+#[cfg(unix)]
+fn file_name_bytes(file_name: &OsStr) -> Cow<'_, [u8]> {
+    Cow::Borrowed(std::os::unix::ffi::OsStrExt::as_bytes(file_name))
+}
+
+#[cfg(not(unix))]
+fn file_name_bytes(file_name: &OsStr) -> Cow<'_, [u8]> {
+    match file_name.to_string_lossy() {
+        Cow::Borrowed(file_name) => Cow::Borrowed(file_name.as_bytes()),
+        Cow::Owned(file_name) => Cow::Owned(file_name.into_bytes()),
+    }
+}
+
+///Escapes file name, returning borrowed input if no escape is necessary.
+fn escape(file_name: &[u8]) -> Cow<'_, [u8]> {
+    if !file_name.iter().any(|byte| matches!(byte, b'\\' | b'\n')) {
+        return Cow::Borrowed(file_name);
+    }
+
+    let mut result = Vec::with_capacity(file_name.len() + 2);
+    for byte in file_name {
+        match byte {
+            b'\\' => result.extend_from_slice(b"\\\\"),
+            b'\n' => result.extend_from_slice(b"\\n"),
+            byte => result.push(*byte),
+        }
+    }
+    Cow::Owned(result)
+}
+
+fn unescape(file_name: &[u8]) -> Option<Vec<u8>> {
+    let mut result = Vec::with_capacity(file_name.len());
+    let mut bytes = file_name.iter();
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'\\' => match bytes.next() {
+                Some(b'\\') => result.push(b'\\'),
+                Some(b'n') => result.push(b'\n'),
+                _ => return None,
+            },
+            byte => result.push(*byte),
+        }
+    }
+    Some(result)
+}
+
+fn find(line: &[u8], pattern: &[u8]) -> Option<usize> {
+    line.windows(pattern.len()).position(|window| window == pattern)
+}
+
+fn rfind(line: &[u8], pattern: &[u8]) -> Option<usize> {
+    line.windows(pattern.len()).rposition(|window| window == pattern)
+}
+
+struct Hash(Algorithm, u128);
+
+impl fmt::Display for Hash {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_hash(self.1, fmt)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Single line of checksum file.
+///
+///File name is kept as raw bytes of the line, so that non UTF-8 names are preserved.
+///Use [write_to](ChecksumLine::write_to) to output line the same way as `xxhsum`,
+///while `Display` replaces invalid UTF-8 sequences.
+pub struct ChecksumLine {
+    ///Hash algorithm
+    pub algorithm: Algorithm,
+    ///Expected hash, zero extended for algorithms with smaller output
+    pub hash: u128,
+    ///Unescaped file name
+    pub file_name: OsString,
+    ///Line style
+    pub format: Format,
+}
+
+impl ChecksumLine {
+    #[inline]
+    ///Creates new line in GNU style.
+    pub fn new(algorithm: Algorithm, hash: u128, file_name: OsString) -> Self {
+        Self {
+            algorithm,
+            hash,
+            file_name,
+            format: Format::Gnu,
+        }
+    }
+
+    #[inline]
+    ///Changes style of line.
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    ///Parses line out of raw bytes, without trailing new line.
+    pub fn from_bytes(line: &[u8]) -> Result<Self, ParseChecksumError> {
+        let (is_escaped, line) = match line.strip_prefix(b"\\") {
+            Some(line) => (true, line),
+            None => (false, line),
+        };
+
+        let bsd_algorithm = find(line, b" (").and_then(|end| {
+            let name = core::str::from_utf8(&line[..end]).ok()?;
+            Algorithm::from_name(name).map(|algorithm| (algorithm, &line[end + 2..]))
+        });
+        let (algorithm, hash, file_name, format) = if let Some((algorithm, line)) = bsd_algorithm {
+            let split = rfind(line, b") = ").ok_or(ParseChecksumError)?;
+            (algorithm, &line[split + 4..], &line[..split], Format::Bsd)
+        } else {
+            let (line, is_xxh3) = match line.strip_prefix(b"XXH3_") {
+                Some(line) => (line, true),
+                None => (line, false),
+            };
+            let split = find(line, b" ").ok_or(ParseChecksumError)?;
+            let (hash, file_name) = (&line[..split], &line[split + 1..]);
+            //Second separator is either space or binary mode marker
+            let file_name = file_name.strip_prefix(b" ").or_else(|| file_name.strip_prefix(b"*")).ok_or(ParseChecksumError)?;
+            let algorithm = match (hash.len(), is_xxh3) {
+                (8, false) => Algorithm::Xxh32,
+                (16, false) => Algorithm::Xxh64,
+                (32, false) => Algorithm::Xxh3_128,
+                (16, true) => Algorithm::Xxh3_64,
+                _ => return Err(ParseChecksumError),
+            };
+            (algorithm, hash, file_name, Format::Gnu)
+        };
+
+        let file_name = match is_escaped {
+            true => unescape(file_name).ok_or(ParseChecksumError)?,
+            false => file_name.to_owned(),
+        };
+
+        if file_name.is_empty() {
+            return Err(ParseChecksumError);
+        }
+
+        let hash = core::str::from_utf8(hash).ok().and_then(|hash| algorithm.parse_hash(hash)).ok_or(ParseChecksumError)?;
+        Ok(Self {
+            algorithm,
+            hash,
+            file_name: file_name_from_bytes(file_name),
+            format,
+        })
+    }
+
+    ///Hashes content of file, returning whether it matches expected hash.
+    pub fn verify(&self) -> io::Result<bool> {
+        let file = fs::File::open(&self.file_name)?;
+        Ok(self.algorithm.hash_reader(file)? == self.hash)
+    }
+
+    ///Writes line the same way as `xxhsum`, without trailing new line.
+    pub fn write_to<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let file_name = file_name_bytes(&self.file_name);
+        let file_name = escape(&file_name);
+        if let Cow::Owned(_) = file_name {
+            out.write_all(b"\\")?;
+        }
+
+        match self.format {
+            Format::Bsd => {
+                write!(out, "{} (", self.algorithm.name())?;
+                out.write_all(&file_name)?;
+                write!(out, ") = {}", Hash(self.algorithm, self.hash))
+            },
+            Format::Gnu => {
+                if self.algorithm == Algorithm::Xxh3_64 {
+                    out.write_all(b"XXH3_")?;
+                }
+                write!(out, "{}  ", Hash(self.algorithm, self.hash))?;
+                out.write_all(&file_name)
+            },
+        }
+    }
+
+    ///Writes verification status line, as produced by `xxhsum -c`, without trailing new line.
+    pub fn write_status<W: Write>(&self, is_ok: bool, mut out: W) -> io::Result<()> {
+        let file_name = file_name_bytes(&self.file_name);
+        let file_name = escape(&file_name);
+        if let Cow::Owned(_) = file_name {
+            out.write_all(b"\\")?;
+        }
+        out.write_all(&file_name)?;
+        out.write_all(if is_ok { b": OK" } else { b": FAILED" })
+    }
+}
+
+impl fmt::Display for ChecksumLine {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut line = Vec::new();
+        self.write_to(&mut line).map_err(|_| fmt::Error)?;
+        fmt.write_str(&String::from_utf8_lossy(&line))
+    }
+}
+
+impl FromStr for ChecksumLine {
+    type Err = ParseChecksumError;
+
+    #[inline(always)]
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(line.as_bytes())
+    }
+}
+
+#[derive(Debug)]
+///Verification status of single line.
+pub enum LineStatus {
+    ///Hash matches.
+    Ok(ChecksumLine),
+    ///Hash doesn't match.
+    Failed(ChecksumLine),
+    ///File cannot be opened or read.
+    ReadFailure(ChecksumLine, io::Error),
+    ///Line cannot be parsed.
+    ImproperlyFormatted,
+}
+
+#[derive(Debug)]
+///Verification result of single line.
+pub struct LineResult {
+    ///Line number, starting from `1`.
+    pub line_number: usize,
+    ///Verification status.
+    pub status: LineStatus,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+///Summary of verification.
+///
+///`Display` formats summary the same way as `xxhsum -c`, one line per kind of problem, each terminated by new line.
+pub struct Report {
+    ///Number of parsed lines.
+    pub properly_formatted: usize,
+    ///Number of lines that couldn't be parsed.
+    pub improperly_formatted: usize,
+    ///Number of files that couldn't be read.
+    pub read_failures: usize,
+    ///Number of matching hashes.
+    pub matched: usize,
+    ///Number of mismatching hashes.
+    pub mismatched: usize,
+}
+
+impl Report {
+    ///Accounts line status.
+    pub fn record(&mut self, status: &LineStatus) {
+        match status {
+            LineStatus::Ok(_) => {
+                self.properly_formatted += 1;
+                self.matched += 1;
+            },
+            LineStatus::Failed(_) => {
+                self.properly_formatted += 1;
+                self.mismatched += 1;
+            },
+            LineStatus::ReadFailure(..) => {
+                self.properly_formatted += 1;
+                self.read_failures += 1;
+            },
+            LineStatus::ImproperlyFormatted => self.improperly_formatted += 1,
+        }
+    }
+
+    ///Returns whether verification succeeded, following `xxhsum -c` exit code.
+    ///
+    ///`strict` requires absence of improperly formatted lines.
+    pub fn is_success(&self, strict: bool) -> bool {
+        self.properly_formatted > 0
+            && self.mismatched == 0
+            && self.read_failures == 0
+            && (!strict || self.improperly_formatted == 0)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.improperly_formatted > 0 {
+            writeln!(fmt, "{} {} improperly formatted", self.improperly_formatted, if self.improperly_formatted == 1 { "line is" } else { "lines are" })?;
+        }
+        if self.read_failures > 0 {
+            writeln!(fmt, "{} listed {} could not be read", self.read_failures, if self.read_failures == 1 { "file" } else { "files" })?;
+        }
+        if self.mismatched > 0 {
+            writeln!(fmt, "{} computed {} did NOT match", self.mismatched, if self.mismatched == 1 { "checksum" } else { "checksums" })?;
+        }
+        Ok(())
+    }
+}
+
+///Verifier of checksum file.
+///
+///Iterates over lines of checksum file, hashing listed files, while accumulating [Report].
+pub struct Verifier<R> {
+    lines: io::Split<R>,
+    line_number: usize,
+    report: Report,
+}
+
+impl<R: BufRead> Verifier<R> {
+    #[inline]
+    ///Creates verifier over content of checksum file.
+    pub fn new(input: R) -> Self {
+        Self {
+            lines: input.split(b'\n'),
+            line_number: 0,
+            report: Report::default(),
+        }
+    }
+
+    #[inline]
+    ///Returns summary of lines verified so far.
+    pub fn report(&self) -> &Report {
+        &self.report
+    }
+
+    ///Verifies all remaining lines, returning summary.
+    pub fn finish(mut self) -> io::Result<Report> {
+        for result in &mut self {
+            result?;
+        }
+        Ok(self.report)
+    }
+}
+
+impl<R: BufRead> Iterator for Verifier<R> {
+    type Item = io::Result<LineResult>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = match self.lines.next()? {
+            Ok(line) => line,
+            Err(error) => return Some(Err(error)),
+        };
+        self.line_number += 1;
+
+        let line = line.strip_suffix(b"\r").unwrap_or(&line);
+        let status = match ChecksumLine::from_bytes(line) {
+            Ok(line) => match line.verify() {
+                Ok(true) => LineStatus::Ok(line),
+                Ok(false) => LineStatus::Failed(line),
+                Err(error) => LineStatus::ReadFailure(line, error),
+            },
+            Err(ParseChecksumError) => LineStatus::ImproperlyFormatted,
+        };
+        self.report.record(&status);
+
+        Some(Ok(LineResult {
+            line_number: self.line_number,
+            status,
+        }))
+    }
+}
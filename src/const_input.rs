@@ -0,0 +1,28 @@
+//!Input conversion for compile time hashing macros.
+
+///Wraps macro input, converting string or byte string into bytes within const context.
+pub struct MacroInput<T>(pub T);
+
+impl<'a> MacroInput<&'a str> {
+    #[inline(always)]
+    ///Returns bytes of string.
+    pub const fn as_bytes(self) -> &'a [u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl<'a> MacroInput<&'a [u8]> {
+    #[inline(always)]
+    ///Returns bytes as they are.
+    pub const fn as_bytes(self) -> &'a [u8] {
+        self.0
+    }
+}
+
+impl<'a, const N: usize> MacroInput<&'a [u8; N]> {
+    #[inline(always)]
+    ///Returns bytes of byte string.
+    pub const fn as_bytes(self) -> &'a [u8] {
+        self.0
+    }
+}
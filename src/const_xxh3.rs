@@ -9,12 +9,89 @@ use crate::xxh32_common as xxh32;
 use crate::xxh64_common as xxh64;
 use crate::xxh3_common::*;
 pub use crate::xxh3_common::const_custom_default_secret;
+pub use crate::canonical::Xxh128Hash;
 
 const INITIAL_ACC: [u64; ACC_NB] = [
     xxh32::PRIME_3 as u64, xxh64::PRIME_1, xxh64::PRIME_2, xxh64::PRIME_3,
     xxh64::PRIME_4, xxh32::PRIME_2 as u64, xxh64::PRIME_5, xxh32::PRIME_1 as u64
 ];
 
+#[macro_export]
+///Computes 64bit xxh3 hash of string or byte string at compile time, with optional seed.
+///
+///Hash is always evaluated in const context, therefore input and seed must be constant.
+///
+///Macro invocation cannot be used as `match` pattern directly.
+///Instead `const NAME = input` form defines named constant, which can be used in patterns:
+///
+///```rust
+///use xxhash_rust::xxh3_64;
+///
+///xxh3_64!(const VALUE = "value");
+///xxh3_64!(pub const SEEDED = b"value", 1);
+///
+///match xxhash_rust::const_xxh3::xxh3_64(b"value") {
+///    VALUE => (),
+///    _ => unreachable!(),
+///}
+///assert_eq!(VALUE, xxh3_64!(b"value"));
+///assert_eq!(SEEDED, xxhash_rust::const_xxh3::xxh3_64_with_seed(b"value", 1));
+///assert_eq!(xxh3_64!("value", 1), SEEDED);
+///```
+macro_rules! xxh3_64 {
+    ($vis:vis const $name:ident = $input:expr) => {
+        $vis const $name: u64 = $crate::xxh3_64!($input, 0);
+    };
+    ($vis:vis const $name:ident = $input:expr, $seed:expr) => {
+        $vis const $name: u64 = $crate::xxh3_64!($input, $seed);
+    };
+    ($input:expr) => {
+        $crate::xxh3_64!($input, 0)
+    };
+    ($input:expr, $seed:expr) => {{
+        const HASH: u64 = $crate::const_xxh3::xxh3_64_with_seed($crate::const_input::MacroInput($input).as_bytes(), $seed);
+        HASH
+    }};
+}
+
+#[macro_export]
+///Computes 128bit xxh3 hash of string or byte string at compile time, with optional seed.
+///
+///Hash is always evaluated in const context, therefore input and seed must be constant.
+///
+///Macro invocation cannot be used as `match` pattern directly.
+///Instead `const NAME = input` form defines named constant, which can be used in patterns:
+///
+///```rust
+///use xxhash_rust::xxh3_128;
+///
+///xxh3_128!(const VALUE = "value");
+///xxh3_128!(pub const SEEDED = b"value", 1);
+///
+///match xxhash_rust::const_xxh3::xxh3_128(b"value") {
+///    VALUE => (),
+///    _ => unreachable!(),
+///}
+///assert_eq!(VALUE, xxh3_128!(b"value"));
+///assert_eq!(SEEDED, xxhash_rust::const_xxh3::xxh3_128_with_seed(b"value", 1));
+///assert_eq!(xxh3_128!("value", 1), SEEDED);
+///```
+macro_rules! xxh3_128 {
+    ($vis:vis const $name:ident = $input:expr) => {
+        $vis const $name: u128 = $crate::xxh3_128!($input, 0);
+    };
+    ($vis:vis const $name:ident = $input:expr, $seed:expr) => {
+        $vis const $name: u128 = $crate::xxh3_128!($input, $seed);
+    };
+    ($input:expr) => {
+        $crate::xxh3_128!($input, 0)
+    };
+    ($input:expr, $seed:expr) => {{
+        const HASH: u128 = $crate::const_xxh3::xxh3_128_with_seed($crate::const_input::MacroInput($input).as_bytes(), $seed);
+        HASH
+    }};
+}
+
 #[inline(always)]
 const fn read_u32(input: &[u8], cursor: usize) -> u32 {
     input[cursor] as u32 | (input[cursor + 1] as u32) << 8 | (input[cursor + 2] as u32) << 16 | (input[cursor + 3] as u32) << 24
@@ -251,6 +328,17 @@ const fn xxh3_64_long_impl(input: &[u8], secret: &[u8]) -> u64 {
     merge_accs(&acc, secret, SECRET_MERGEACCS_START, (input.len() as u64).wrapping_mul(xxh64::PRIME_1))
 }
 
+#[inline(always)]
+const fn xxh3_64_short(input: &[u8], seed: u64, secret: &[u8]) -> u64 {
+    if input.len() <= 16 {
+        xxh3_64_0to16(input, seed, secret)
+    } else if input.len() <= 128 {
+        xxh3_64_7to128(input, seed, secret)
+    } else {
+        xxh3_64_129to240(input, seed, secret)
+    }
+}
+
 #[inline(always)]
 ///Returns 64bit hash for provided input.
 pub const fn xxh3_64(input: &[u8]) -> u64 {
@@ -259,25 +347,22 @@ pub const fn xxh3_64(input: &[u8]) -> u64 {
 
 ///Returns 64bit hash for provided input using seed.
 pub const fn xxh3_64_with_seed(input: &[u8], seed: u64) -> u64 {
-    if input.len() <= 16 {
-        xxh3_64_0to16(input, seed, &DEFAULT_SECRET)
-    } else if input.len() <= 128 {
-        xxh3_64_7to128(input, seed, &DEFAULT_SECRET)
-    } else if input.len() <= MID_SIZE_MAX {
-        xxh3_64_129to240(input, seed, &DEFAULT_SECRET)
+    if input.len() <= MID_SIZE_MAX {
+        xxh3_64_short(input, seed, &DEFAULT_SECRET)
     } else {
         xxh3_64_long_impl(input, &const_custom_default_secret(seed))
     }
 }
 
 ///Returns 64bit hash for provided input using custom secret.
-pub const fn xxh3_64_with_secret(input: &[u8], secret: &[u8; DEFAULT_SECRET_SIZE]) -> u64 {
-    if input.len() <= 16 {
-        xxh3_64_0to16(input, 0, secret)
-    } else if input.len() <= 128 {
-        xxh3_64_7to128(input, 0, secret)
-    } else if input.len() <= MID_SIZE_MAX {
-        xxh3_64_129to240(input, 0, secret)
+///
+///Secret can be of any length equal or greater than `SECRET_SIZE_MIN`.
+///This function panics on insufficient secret length, failing compilation when evaluated in const context.
+pub const fn xxh3_64_with_secret(input: &[u8], secret: &[u8]) -> u64 {
+    assert!(secret.len() >= SECRET_SIZE_MIN, "secret length must be equal or greater than SECRET_SIZE_MIN=136");
+
+    if input.len() <= MID_SIZE_MAX {
+        xxh3_64_short(input, 0, secret)
     } else {
         xxh3_64_long_impl(input, secret)
     }
@@ -434,6 +519,17 @@ const fn xxh3_128_long_impl(input: &[u8], secret: &[u8]) -> u128 {
     lo as u128 | (hi as u128) << 64
 }
 
+#[inline(always)]
+const fn xxh3_128_short(input: &[u8], seed: u64, secret: &[u8]) -> u128 {
+    if input.len() <= 16 {
+        xxh3_128_0to16(input, seed, secret)
+    } else if input.len() <= 128 {
+        xxh3_128_7to128(input, seed, secret)
+    } else {
+        xxh3_128_129to240(input, seed, secret)
+    }
+}
+
 #[inline(always)]
 ///Returns 128 hash for provided input.
 pub const fn xxh3_128(input: &[u8]) -> u128 {
@@ -442,26 +538,489 @@ pub const fn xxh3_128(input: &[u8]) -> u128 {
 
 ///Returns 128 hash for provided input using seed.
 pub const fn xxh3_128_with_seed(input: &[u8], seed: u64) -> u128 {
-    if input.len() <= 16 {
-        xxh3_128_0to16(input, seed, &DEFAULT_SECRET)
-    } else if input.len() <= 128 {
-        xxh3_128_7to128(input, seed, &DEFAULT_SECRET)
-    } else if input.len() <= MID_SIZE_MAX {
-        xxh3_128_129to240(input, seed, &DEFAULT_SECRET)
+    if input.len() <= MID_SIZE_MAX {
+        xxh3_128_short(input, seed, &DEFAULT_SECRET)
     } else {
         xxh3_128_long_impl(input, &const_custom_default_secret(seed))
     }
 }
 
 ///Returns 128 hash for provided input using custom secret.
-pub const fn xxh3_128_with_secret(input: &[u8], secret: &[u8; DEFAULT_SECRET_SIZE]) -> u128 {
-    if input.len() <= 16 {
-        xxh3_128_0to16(input, 0, secret)
-    } else if input.len() <= 128 {
-        xxh3_128_7to128(input, 0, secret)
-    } else if input.len() <= MID_SIZE_MAX {
-        xxh3_128_129to240(input, 0, secret)
+///
+///Secret can be of any length equal or greater than `SECRET_SIZE_MIN`.
+///This function panics on insufficient secret length, failing compilation when evaluated in const context.
+pub const fn xxh3_128_with_secret(input: &[u8], secret: &[u8]) -> u128 {
+    assert!(secret.len() >= SECRET_SIZE_MIN, "secret length must be equal or greater than SECRET_SIZE_MIN=136");
+
+    if input.len() <= MID_SIZE_MAX {
+        xxh3_128_short(input, 0, secret)
     } else {
         xxh3_128_long_impl(input, secret)
     }
 }
+
+///Generates secret of arbitrary length from provided seed material.
+///
+///Equivalent of `XXH3_generateSecret` from reference implementation, producing identical output.
+///`seed_material` can be of any length, including empty, in which case default secret is used as seed material.
+///
+///On insufficient secret length it shall panic.
+pub const fn generate_secret<const N: usize>(seed_material: &[u8]) -> [u8; N] {
+    assert!(N >= SECRET_SIZE_MIN, "secret length must be equal or greater than SECRET_SIZE_MIN=136");
+
+    let seed_material: &[u8] = if seed_material.is_empty() {
+        &DEFAULT_SECRET
+    } else {
+        seed_material
+    };
+
+    let mut result = [0; N];
+    let mut idx = 0;
+    while idx < N {
+        result[idx] = seed_material[idx % seed_material.len()];
+        idx += 1;
+    }
+
+    const fn combine16<const N: usize>(mut secret: [u8; N], offset: usize, hash: u128) -> [u8; N] {
+        let hash = hash.to_le_bytes();
+        let mut idx = 0;
+        while idx < 16 {
+            secret[offset + idx] ^= hash[idx];
+            idx += 1;
+        }
+        secret
+    }
+
+    let scrambler = xxh3_128_with_seed(seed_material, 0).to_be_bytes();
+    let nb_seg16 = N / 16;
+    idx = 0;
+    while idx < nb_seg16 {
+        result = combine16(result, idx * 16, xxh3_128_with_seed(&scrambler, idx as u64));
+        idx += 1;
+    }
+
+    combine16(result, N - 16, u128::from_be_bytes(scrambler))
+}
+
+//
+//Streaming
+//
+
+const INTERNAL_BUFFER_SIZE: usize = 256;
+const INTERNAL_BUFFER_STRIPES: usize = INTERNAL_BUFFER_SIZE / STRIPE_LEN;
+
+#[inline(always)]
+const fn buffered_input(buffer: &[u8], len: usize) -> &[u8] {
+    assert!(len <= buffer.len());
+    unsafe {
+        core::slice::from_raw_parts(buffer.as_ptr(), len)
+    }
+}
+
+#[inline(always)]
+const fn copy_to_buffer<const N: usize>(mut buffer: [u8; N], offset: usize, input: &[u8], input_offset: usize, len: usize) -> [u8; N] {
+    let mut idx = 0;
+    while idx < len {
+        buffer[offset + idx] = input[input_offset + idx];
+        idx += 1;
+    }
+
+    buffer
+}
+
+const fn consume_stripes(mut acc: [u64; ACC_NB], nb_stripes: usize, nb_stripes_acc: usize, input: &[u8], input_offset: usize, secret: &[u8]) -> ([u64; ACC_NB], usize) {
+    let stripes_per_block = (secret.len() - STRIPE_LEN) / SECRET_CONSUME_RATE;
+
+    if (stripes_per_block - nb_stripes_acc) <= nb_stripes {
+        let stripes_to_end = stripes_per_block - nb_stripes_acc;
+        let stripes_after_end = nb_stripes - stripes_to_end;
+
+        acc = accumulate_loop(acc, input, input_offset, secret, nb_stripes_acc * SECRET_CONSUME_RATE, stripes_to_end);
+        acc = scramble_acc(acc, secret, secret.len() - STRIPE_LEN);
+        acc = accumulate_loop(acc, input, input_offset + stripes_to_end * STRIPE_LEN, secret, 0, stripes_after_end);
+        (acc, stripes_after_end)
+    } else {
+        acc = accumulate_loop(acc, input, input_offset, secret, nb_stripes_acc * SECRET_CONSUME_RATE, nb_stripes);
+        (acc, nb_stripes_acc + nb_stripes)
+    }
+}
+
+#[derive(Clone, Copy)]
+///Const variant of XXH3 streaming algorithm.
+///
+///Produces the same result as runtime `Xxh3` while being usable in const context:
+///
+///```rust
+///use xxhash_rust::const_xxh3::{xxh3_64, ConstXxh3};
+///
+///const HASH: u64 = ConstXxh3::new().update(b"module::").update(b"Type").digest();
+///assert_eq!(HASH, xxh3_64(b"module::Type"));
+///```
+pub struct ConstXxh3 {
+    acc: [u64; ACC_NB],
+    secret: [u8; DEFAULT_SECRET_SIZE],
+    buffer: [u8; INTERNAL_BUFFER_SIZE],
+    buffered_size: usize,
+    nb_stripes_acc: usize,
+    total_len: u64,
+    seed: u64,
+}
+
+impl ConstXxh3 {
+    #[inline(always)]
+    ///Creates new hasher with default settings
+    pub const fn new() -> Self {
+        Self::with_custom_ops(0, DEFAULT_SECRET)
+    }
+
+    #[inline]
+    const fn with_custom_ops(seed: u64, secret: [u8; DEFAULT_SECRET_SIZE]) -> Self {
+        Self {
+            acc: INITIAL_ACC,
+            secret,
+            buffer: [0; INTERNAL_BUFFER_SIZE],
+            buffered_size: 0,
+            nb_stripes_acc: 0,
+            total_len: 0,
+            seed,
+        }
+    }
+
+    #[inline(always)]
+    ///Creates new hasher with custom seed.
+    pub const fn with_seed(seed: u64) -> Self {
+        Self::with_custom_ops(seed, const_custom_default_secret(seed))
+    }
+
+    #[inline(always)]
+    ///Creates new hasher with custom secret.
+    pub const fn with_secret(secret: [u8; DEFAULT_SECRET_SIZE]) -> Self {
+        Self::with_custom_ops(0, secret)
+    }
+
+    ///Hashes provided input, returning updated state.
+    pub const fn update(mut self, input: &[u8]) -> Self {
+        let mut cursor = 0;
+        self.total_len = self.total_len.wrapping_add(input.len() as u64);
+
+        if (self.buffered_size + input.len()) <= INTERNAL_BUFFER_SIZE {
+            self.buffer = copy_to_buffer(self.buffer, self.buffered_size, input, 0, input.len());
+            self.buffered_size += input.len();
+            return self;
+        }
+
+        if self.buffered_size > 0 {
+            let fill_len = INTERNAL_BUFFER_SIZE - self.buffered_size;
+            self.buffer = copy_to_buffer(self.buffer, self.buffered_size, input, 0, fill_len);
+            cursor += fill_len;
+
+            let (acc, nb_stripes_acc) = consume_stripes(self.acc, INTERNAL_BUFFER_STRIPES, self.nb_stripes_acc, &self.buffer, 0, &self.secret);
+            self.acc = acc;
+            self.nb_stripes_acc = nb_stripes_acc;
+            self.buffered_size = 0;
+        }
+
+        if (input.len() - cursor) > INTERNAL_BUFFER_SIZE {
+            loop {
+                let (acc, nb_stripes_acc) = consume_stripes(self.acc, INTERNAL_BUFFER_STRIPES, self.nb_stripes_acc, input, cursor, &self.secret);
+                self.acc = acc;
+                self.nb_stripes_acc = nb_stripes_acc;
+                cursor += INTERNAL_BUFFER_SIZE;
+
+                if (input.len() - cursor) <= INTERNAL_BUFFER_SIZE {
+                    break;
+                }
+            }
+
+            //Keep last consumed stripe in tail of the buffer in case less than a stripe remains.
+            self.buffer = copy_to_buffer(self.buffer, INTERNAL_BUFFER_SIZE - STRIPE_LEN, input, cursor - STRIPE_LEN, STRIPE_LEN);
+        }
+
+        self.buffer = copy_to_buffer(self.buffer, 0, input, cursor, input.len() - cursor);
+        self.buffered_size = input.len() - cursor;
+        self
+    }
+
+    const fn digest_acc(&self) -> [u64; ACC_NB] {
+        let secret = &self.secret;
+
+        if self.buffered_size >= STRIPE_LEN {
+            let nb_stripes = (self.buffered_size - 1) / STRIPE_LEN;
+            let (acc, _) = consume_stripes(self.acc, nb_stripes, self.nb_stripes_acc, &self.buffer, 0, secret);
+
+            accumulate_512(acc, &self.buffer, self.buffered_size - STRIPE_LEN, secret, secret.len() - STRIPE_LEN - SECRET_LASTACC_START)
+        } else {
+            let catchup_size = STRIPE_LEN - self.buffered_size;
+            let last_stripe = copy_to_buffer([0; STRIPE_LEN], 0, &self.buffer, INTERNAL_BUFFER_SIZE - catchup_size, catchup_size);
+            let last_stripe = copy_to_buffer(last_stripe, catchup_size, &self.buffer, 0, self.buffered_size);
+
+            accumulate_512(self.acc, &last_stripe, 0, secret, secret.len() - STRIPE_LEN - SECRET_LASTACC_START)
+        }
+    }
+
+    ///Computes hash.
+    pub const fn digest(&self) -> u64 {
+        if self.total_len > MID_SIZE_MAX as u64 {
+            let acc = self.digest_acc();
+            merge_accs(&acc, &self.secret, SECRET_MERGEACCS_START, self.total_len.wrapping_mul(xxh64::PRIME_1))
+        } else if self.seed > 0 {
+            //Seeded hash uses default secret for input with size less or equal to MID_SIZE_MAX
+            xxh3_64_short(buffered_input(&self.buffer, self.buffered_size), self.seed, &DEFAULT_SECRET)
+        } else {
+            xxh3_64_short(buffered_input(&self.buffer, self.buffered_size), 0, &self.secret)
+        }
+    }
+
+    ///Computes hash as 128bit integer.
+    pub const fn digest128(&self) -> u128 {
+        if self.total_len > MID_SIZE_MAX as u64 {
+            let acc = self.digest_acc();
+            let secret = &self.secret;
+            let lo = merge_accs(&acc, secret, SECRET_MERGEACCS_START, self.total_len.wrapping_mul(xxh64::PRIME_1));
+            let hi = merge_accs(&acc,
+                                secret, secret.len() - ACC_NB * mem::size_of::<u64>() - SECRET_MERGEACCS_START,
+                                !self.total_len.wrapping_mul(xxh64::PRIME_2));
+
+            lo as u128 | (hi as u128) << 64
+        } else if self.seed > 0 {
+            xxh3_128_short(buffered_input(&self.buffer, self.buffered_size), self.seed, &DEFAULT_SECRET)
+        } else {
+            xxh3_128_short(buffered_input(&self.buffer, self.buffered_size), 0, &self.secret)
+        }
+    }
+}
+
+impl Default for ConstXxh3 {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//
+//Static map
+//
+
+#[inline(always)]
+const fn static_map_bucket(hash: u64, len: usize) -> usize {
+    ((hash >> 32) % len as u64) as usize
+}
+
+#[inline(always)]
+const fn static_map_slot(hash: u64, displacement: u64, len: usize) -> usize {
+    (avalanche(hash ^ displacement) % len as u64) as usize
+}
+
+#[cfg(feature = "xxh3")]
+#[inline(always)]
+fn static_map_hash(key: &str) -> u64 {
+    crate::xxh3::xxh3_64(key.as_bytes())
+}
+
+#[cfg(not(feature = "xxh3"))]
+#[inline(always)]
+fn static_map_hash(key: &str) -> u64 {
+    xxh3_64(key.as_bytes())
+}
+
+#[derive(Clone, Copy)]
+///Static string keyed map, built at compile time.
+///
+///Keys are placed using perfect hashing over `xxh3_64`, therefore lookup requires single probe.
+///Construction panics on duplicate keys or hash collisions, failing compilation when evaluated in const context.
+///
+///Lookup uses runtime `xxh3_64` when `xxh3` feature is enabled.
+///
+///```rust
+///use xxhash_rust::const_xxh3::StaticMap;
+///
+///static COMMANDS: StaticMap<u8, 3> = StaticMap::new([("get", 0), ("set", 1), ("del", 2)]);
+///
+///assert_eq!(COMMANDS.get("set"), Some(&1));
+///assert_eq!(COMMANDS.get("put"), None);
+///```
+pub struct StaticMap<V, const N: usize> {
+    entries: [(&'static str, V); N],
+    displacements: [u64; N],
+    slots: [usize; N],
+}
+
+impl<V, const N: usize> StaticMap<V, N> {
+    const EMPTY_SLOT: usize = usize::MAX;
+    //Single key bucket placed last has one free slot, found within `N` attempts on average
+    const MAX_DISPLACEMENT: u64 = N as u64 * 16 + 256;
+
+    ///Creates new map out of provided entries.
+    ///
+    ///Panics if any two keys are equal or have the same hash,
+    ///or if perfect hashing fails to place keys within bounded number of attempts.
+    pub const fn new(entries: [(&'static str, V); N]) -> Self {
+        let mut hashes = [0u64; N];
+        let mut bucket_sizes = [0usize; N];
+        let mut idx = 0;
+        while idx < N {
+            hashes[idx] = xxh3_64(entries[idx].0.as_bytes());
+            bucket_sizes[static_map_bucket(hashes[idx], N)] += 1;
+            idx += 1;
+        }
+
+        //Counting sort of keys by bucket, so that each bucket's keys are adjacent within `order`
+        let mut bucket_starts = [0usize; N];
+        let mut max_bucket_size = 0;
+        let mut bucket = 0;
+        let mut start = 0;
+        while bucket < N {
+            bucket_starts[bucket] = start;
+            start += bucket_sizes[bucket];
+            if bucket_sizes[bucket] > max_bucket_size {
+                max_bucket_size = bucket_sizes[bucket];
+            }
+            bucket += 1;
+        }
+
+        let mut order = [0usize; N];
+        let mut bucket_fill = [0usize; N];
+        idx = 0;
+        while idx < N {
+            let bucket = static_map_bucket(hashes[idx], N);
+            order[bucket_starts[bucket] + bucket_fill[bucket]] = idx;
+            bucket_fill[bucket] += 1;
+            idx += 1;
+        }
+
+        //Equal hashes always share bucket, hence only keys within the same bucket need to be compared
+        bucket = 0;
+        while bucket < N {
+            let start = bucket_starts[bucket];
+            let end = start + bucket_sizes[bucket];
+            let mut left = start;
+            while left < end {
+                let mut right = left + 1;
+                while right < end {
+                    if hashes[order[left]] == hashes[order[right]] {
+                        if const_str_eq(entries[order[left]].0, entries[order[right]].0) {
+                            panic!("StaticMap contains duplicate key");
+                        } else {
+                            panic!("StaticMap contains keys with colliding hashes");
+                        }
+                    }
+                    right += 1;
+                }
+                left += 1;
+            }
+            bucket += 1;
+        }
+
+        //Place largest buckets first, searching displacement which maps all bucket keys onto free slots.
+        let mut displacements = [0u64; N];
+        let mut slots = [Self::EMPTY_SLOT; N];
+        let mut bucket_size = max_bucket_size;
+        while bucket_size > 0 {
+            bucket = 0;
+            while bucket < N {
+                if bucket_sizes[bucket] == bucket_size {
+                    let start = bucket_starts[bucket];
+                    let end = start + bucket_size;
+                    let mut displacement = 0;
+                    loop {
+                        let mut placed_end = start;
+                        while placed_end < end {
+                            let slot = static_map_slot(hashes[order[placed_end]], displacement, N);
+                            if slots[slot] != Self::EMPTY_SLOT {
+                                break;
+                            }
+                            slots[slot] = order[placed_end];
+                            placed_end += 1;
+                        }
+
+                        if placed_end == end {
+                            break;
+                        }
+
+                        //Release slots taken by this bucket before trying next displacement
+                        idx = start;
+                        while idx < placed_end {
+                            slots[static_map_slot(hashes[order[idx]], displacement, N)] = Self::EMPTY_SLOT;
+                            idx += 1;
+                        }
+                        displacement += 1;
+                        if displacement == Self::MAX_DISPLACEMENT {
+                            panic!("StaticMap failed to find displacement placing all keys, consider changing key set");
+                        }
+                    }
+
+                    displacements[bucket] = displacement;
+                }
+                bucket += 1;
+            }
+            bucket_size -= 1;
+        }
+
+        Self {
+            entries,
+            displacements,
+            slots,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns number of entries.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    #[inline(always)]
+    ///Returns whether map has no entries.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    #[inline]
+    ///Returns value associated with `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        if N == 0 {
+            return None;
+        }
+
+        let hash = static_map_hash(key);
+        let displacement = self.displacements[static_map_bucket(hash, N)];
+        let entry = &self.entries[self.slots[static_map_slot(hash, displacement, N)]];
+
+        if entry.0 == key {
+            Some(&entry.1)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    ///Returns whether map contains `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    #[inline]
+    ///Returns iterator over entries in order of construction.
+    pub fn iter(&self) -> core::slice::Iter<'_, (&'static str, V)> {
+        self.entries.iter()
+    }
+}
+
+const fn const_str_eq(left: &str, right: &str) -> bool {
+    let left = left.as_bytes();
+    let right = right.as_bytes();
+
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut idx = 0;
+    while idx < left.len() {
+        if left[idx] != right[idx] {
+            return false;
+        }
+        idx += 1;
+    }
+
+    true
+}
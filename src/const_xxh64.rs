@@ -4,6 +4,44 @@ use core::mem;
 
 use crate::xxh64_common::*;
 
+#[macro_export]
+///Computes xxh64 hash of string or byte string at compile time, with optional seed.
+///
+///Hash is always evaluated in const context, therefore input and seed must be constant.
+///
+///Macro invocation cannot be used as `match` pattern directly.
+///Instead `const NAME = input` form defines named constant, which can be used in patterns:
+///
+///```rust
+///use xxhash_rust::xxh64;
+///
+///xxh64!(const VALUE = "value");
+///xxh64!(pub const SEEDED = b"value", 1);
+///
+///match xxhash_rust::const_xxh64::xxh64(b"value", 0) {
+///    VALUE => (),
+///    _ => unreachable!(),
+///}
+///assert_eq!(VALUE, xxh64!(b"value"));
+///assert_eq!(SEEDED, xxhash_rust::const_xxh64::xxh64(b"value", 1));
+///assert_eq!(xxh64!("value", 1), SEEDED);
+///```
+macro_rules! xxh64 {
+    ($vis:vis const $name:ident = $input:expr) => {
+        $vis const $name: u64 = $crate::xxh64!($input, 0);
+    };
+    ($vis:vis const $name:ident = $input:expr, $seed:expr) => {
+        $vis const $name: u64 = $crate::xxh64!($input, $seed);
+    };
+    ($input:expr) => {
+        $crate::xxh64!($input, 0)
+    };
+    ($input:expr, $seed:expr) => {{
+        const HASH: u64 = $crate::const_xxh64::xxh64($crate::const_input::MacroInput($input).as_bytes(), $seed);
+        HASH
+    }};
+}
+
 #[inline(always)]
 const fn read_u32(input: &[u8], cursor: usize) -> u32 {
     input[cursor] as u32 | (input[cursor + 1] as u32) << 8 | (input[cursor + 2] as u32) << 16 | (input[cursor + 3] as u32) << 24
@@ -91,3 +129,112 @@ pub const fn xxh64(input: &[u8], seed: u64) -> u64 {
 
     finalize(result, input, cursor)
 }
+
+#[inline(always)]
+const fn buffered_input(buffer: &[u8], len: usize) -> &[u8] {
+    assert!(len <= buffer.len());
+    unsafe {
+        core::slice::from_raw_parts(buffer.as_ptr(), len)
+    }
+}
+
+#[inline(always)]
+const fn consume_chunk(mut v: (u64, u64, u64, u64), input: &[u8], cursor: usize) -> (u64, u64, u64, u64) {
+    v.0 = round(v.0, read_u64(input, cursor));
+    v.1 = round(v.1, read_u64(input, cursor + 8));
+    v.2 = round(v.2, read_u64(input, cursor + 16));
+    v.3 = round(v.3, read_u64(input, cursor + 24));
+    v
+}
+
+#[derive(Clone, Copy)]
+///Const variant of xxh64 streaming algorithm.
+///
+///Produces the same result as runtime `Xxh64` while being usable in const context:
+///
+///```rust
+///use xxhash_rust::const_xxh64::{xxh64, ConstXxh64};
+///
+///const HASH: u64 = ConstXxh64::new(0).update(b"module::").update(b"Type").digest();
+///assert_eq!(HASH, xxh64(b"module::Type", 0));
+///```
+pub struct ConstXxh64 {
+    total_len: u64,
+    v: (u64, u64, u64, u64),
+    buffer: [u8; CHUNK_SIZE],
+    buffered_size: usize,
+}
+
+impl ConstXxh64 {
+    #[inline]
+    ///Creates new hasher with specified seed.
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            total_len: 0,
+            v: (
+                seed.wrapping_add(PRIME_1).wrapping_add(PRIME_2),
+                seed.wrapping_add(PRIME_2),
+                seed,
+                seed.wrapping_sub(PRIME_1),
+            ),
+            buffer: [0; CHUNK_SIZE],
+            buffered_size: 0,
+        }
+    }
+
+    ///Hashes provided input, returning updated state.
+    pub const fn update(mut self, input: &[u8]) -> Self {
+        let mut cursor = 0;
+        self.total_len = self.total_len.wrapping_add(input.len() as u64);
+
+        if self.buffered_size > 0 {
+            while self.buffered_size < CHUNK_SIZE && cursor < input.len() {
+                self.buffer[self.buffered_size] = input[cursor];
+                self.buffered_size += 1;
+                cursor += 1;
+            }
+
+            if self.buffered_size < CHUNK_SIZE {
+                return self;
+            }
+
+            self.v = consume_chunk(self.v, &self.buffer, 0);
+            self.buffered_size = 0;
+        }
+
+        while input.len() - cursor >= CHUNK_SIZE {
+            self.v = consume_chunk(self.v, input, cursor);
+            cursor += CHUNK_SIZE;
+        }
+
+        while cursor < input.len() {
+            self.buffer[self.buffered_size] = input[cursor];
+            self.buffered_size += 1;
+            cursor += 1;
+        }
+
+        self
+    }
+
+    ///Computes hash.
+    pub const fn digest(&self) -> u64 {
+        let mut result;
+
+        if self.total_len >= CHUNK_SIZE as u64 {
+            result = self.v.0.rotate_left(1).wrapping_add(self.v.1.rotate_left(7))
+                                            .wrapping_add(self.v.2.rotate_left(12))
+                                            .wrapping_add(self.v.3.rotate_left(18));
+
+            result = merge_round(result, self.v.0);
+            result = merge_round(result, self.v.1);
+            result = merge_round(result, self.v.2);
+            result = merge_round(result, self.v.3);
+        } else {
+            result = self.v.2.wrapping_add(PRIME_5)
+        }
+
+        result = result.wrapping_add(self.total_len);
+
+        finalize(result, buffered_input(&self.buffer, self.buffered_size), 0)
+    }
+}
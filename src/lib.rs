@@ -37,19 +37,34 @@
 //!
 //!By default all features are off.
 //!
-//!- `std` - Enables `std::io::Write` trait implementation
+//!- `std` - Enables `std::io::Write` trait implementation and `hash_reader`/`hash_file` helpers
 //!- `xxh32` - Enables 32bit algorithm. Suitable for x86 targets
-//!- `const_xxh32` - `const fn` version of `xxh32` algorithm
+//!- `const_xxh32` - `const fn` version of `xxh32` algorithm and `xxh32!` macro
 //!- `xxh64` - Enables 64 algorithm. Suitable for x86_64 targets
-//!- `const_xxh64` - `const fn` version of `xxh64` algorithm
+//!- `const_xxh64` - `const fn` version of `xxh64` algorithm and `xxh64!` macro
 //!- `xxh3` - Enables `xxh3` family of algorithms, superior to `xxh32` and `xxh64` in terms of performance.
-//!- `const_xxh3` - `const fn` version of `xxh3` algorithm
+//!- `const_xxh3` - `const fn` version of `xxh3` algorithm and `xxh3_64!`/`xxh3_128!` macros
+//!- `runtime_dispatch` - Selects `xxh3` SIMD acceleration at runtime, depending on CPU capabilities. Implies `std` and `xxh3`
+//!- `serde` - Enables serialization of streaming states and hash values via `serde`.
+//!- `digest` - Implements RustCrypto `digest` traits (`Update`, `FixedOutput`) for hashers, with output in canonical big-endian form. `Reset` is implemented by `xxh3` hashers and by `xxh32::Xxh32Digest`/`xxh64::Xxh64Digest` wrappers, which retain seed.
+//!- `mmap` - Enables `hash_file_mmap` functions, hashing memory-mapped files via one-shot algorithms. Requires `std`
+//!- `rayon` - Enables `xxh3::batch` module, hashing many inputs in parallel via `rayon`. Implies `std` and `xxh3`
+//!- `checksum` - Enables `checksum` module, parsing, formatting and verifying `xxhsum` checksum files. Requires `std`
+//!- `cli` - Builds `xxhsum` command line utility, compatible with reference implementation.
+//!
+//!Compile time macros `xxh32!`, `xxh64!`, `xxh3_64!` and `xxh3_128!` accept string or byte string with optional seed.
+//!Macro invocation cannot be used as `match` pattern directly, instead form `xxh3_64!(const NAME = "input")` defines named constant usable in patterns.
 //!
 //!## HW acceleration
 //!
 //!Similar to reference implementation, crate implements various SIMDs in `xxh3` depending on provided flags.
 //!All checks are performed only at compile time, hence user is encouraged to enable these accelerations (for example via `-C target_cpu=native`)
 //!
+//!Alternatively `runtime_dispatch` feature can be used to detect best available x86 acceleration (SSE2, AVX2 or AVX512) on first use of long input.
+//!Backend can be inspected or forced via `xxh3::Xxh3Backend`.
+//!
+//!Portable implementation without SIMD is always compiled and available via `xxh3::scalar` as reference.
+//!
 //!Used SIMD acceleration:
 //!
 //!- SSE2 - widely available, can be safely enabled in 99% of cases. Enabled by default in `x86_64` targets.
@@ -85,6 +100,12 @@ extern crate std;
 #[cfg(any(feature = "xxh32", feature = "xxh3", feature = "xxh64"))]
 mod utils;
 
+pub mod canonical;
+#[cfg(any(feature = "xxh32", feature = "xxh3", feature = "xxh64"))]
+pub mod state;
+#[cfg(feature = "serde")]
+mod serde_utils;
+
 #[cfg(any(feature = "xxh32", feature = "const_xxh32", feature = "xxh3", feature = "const_xxh3"))]
 mod xxh32_common;
 #[cfg(feature = "xxh32")]
@@ -105,3 +126,10 @@ mod xxh3_common;
 pub mod xxh3;
 #[cfg(feature = "const_xxh3")]
 pub mod const_xxh3;
+
+#[cfg(any(feature = "const_xxh32", feature = "const_xxh64", feature = "const_xxh3"))]
+#[doc(hidden)]
+pub mod const_input;
+
+#[cfg(feature = "checksum")]
+pub mod checksum;
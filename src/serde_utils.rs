@@ -0,0 +1,73 @@
+//! Helpers for `serde` support
+use core::{fmt, marker};
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+#[derive(Clone, Copy)]
+///Fixed capacity byte buffer, serialized as bytes.
+pub struct ByteBuf<const N: usize> {
+    len: usize,
+    data: [u8; N],
+}
+
+impl<const N: usize> ByteBuf<N> {
+    #[inline(always)]
+    pub fn new(bytes: &[u8]) -> Self {
+        let mut result = Self {
+            len: bytes.len(),
+            data: [0; N],
+        };
+        result.data[..bytes.len()].copy_from_slice(bytes);
+        result
+    }
+
+    #[inline(always)]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+}
+
+impl<const N: usize> Serialize for ByteBuf<N> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.as_slice())
+    }
+}
+
+struct ByteBufVisitor<const N: usize>(marker::PhantomData<[u8; N]>);
+
+impl<'de, const N: usize> Visitor<'de> for ByteBufVisitor<N> {
+    type Value = ByteBuf<N>;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "at most {} bytes", N)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        if bytes.len() > N {
+            Err(E::invalid_length(bytes.len(), &self))
+        } else {
+            Ok(ByteBuf::new(bytes))
+        }
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut result = ByteBuf::new(&[]);
+        while let Some(byte) = seq.next_element()? {
+            if result.len == N {
+                return Err(de::Error::invalid_length(result.len + 1, &self));
+            }
+            result.data[result.len] = byte;
+            result.len += 1;
+        }
+        Ok(result)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for ByteBuf<N> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(ByteBufVisitor(marker::PhantomData))
+    }
+}
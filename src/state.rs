@@ -0,0 +1,169 @@
+//!Binary export of streaming states
+//!
+//!Streaming hashers can be exported via `to_bytes` into fixed size array and restored via `from_bytes`,
+//!producing the same digest as uninterrupted hasher.
+//!
+//!## Layout
+//!
+//!All integers are stored as little-endian, regardless of platform:
+//!
+//!- 3 bytes tag, identifying hasher (`X32`, `X64` or `X3D`);
+//!- 1 byte format version;
+//!- Hasher specific fields, described in its `to_bytes` documentation;
+//!- Checksum of all preceding bytes, computed using the same algorithm as hasher with seed `0`.
+//!
+//!Corrupted or foreign state is rejected with [StateError].
+
+use core::fmt;
+
+///Current version of binary state format.
+pub const STATE_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///Error restoring streaming state.
+pub enum StateError {
+    ///State belongs to different hasher.
+    InvalidTag,
+    ///State format version is not supported.
+    UnsupportedVersion,
+    ///State checksum doesn't match its content.
+    ChecksumMismatch,
+    ///State contains inconsistent values.
+    InvalidState,
+}
+
+impl fmt::Display for StateError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTag => fmt.write_str("state belongs to different hasher"),
+            Self::UnsupportedVersion => fmt.write_str("unsupported state version"),
+            Self::ChecksumMismatch => fmt.write_str("state checksum mismatch"),
+            Self::InvalidState => fmt.write_str("inconsistent state"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StateError {}
+
+pub(crate) const HEADER_SIZE: usize = 4;
+
+pub(crate) struct StateWriter<'a> {
+    bytes: &'a mut [u8],
+    cursor: usize,
+}
+
+impl<'a> StateWriter<'a> {
+    #[inline(always)]
+    pub(crate) fn new(bytes: &'a mut [u8], tag: &[u8; 3]) -> Self {
+        let mut result = Self {
+            bytes,
+            cursor: 0,
+        };
+        result.write_bytes(tag);
+        result.write_u8(STATE_VERSION);
+        result
+    }
+
+    #[inline(always)]
+    pub(crate) fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes[self.cursor..self.cursor + bytes.len()].copy_from_slice(bytes);
+        self.cursor += bytes.len();
+    }
+
+    #[inline(always)]
+    pub(crate) fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    #[allow(unused)]
+    #[inline(always)]
+    pub(crate) fn write_u16(&mut self, value: u16) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    #[allow(unused)]
+    #[inline(always)]
+    pub(crate) fn write_u32(&mut self, value: u32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    #[allow(unused)]
+    #[inline(always)]
+    pub(crate) fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    #[inline(always)]
+    ///Returns written bytes, to be covered by checksum.
+    pub(crate) fn written(&self) -> &[u8] {
+        &self.bytes[..self.cursor]
+    }
+}
+
+pub(crate) struct StateReader<'a> {
+    bytes: &'a [u8],
+    cursor: usize,
+}
+
+impl<'a> StateReader<'a> {
+    #[inline(always)]
+    ///Validates header and checksum, computed by `checksum` over all bytes before `checksum_size` trailer.
+    pub(crate) fn new(bytes: &'a [u8], tag: &[u8; 3], checksum_size: usize, checksum: impl FnOnce(&[u8]) -> u64) -> Result<Self, StateError> {
+        if bytes[..3] != tag[..] {
+            return Err(StateError::InvalidTag);
+        } else if bytes[3] != STATE_VERSION {
+            return Err(StateError::UnsupportedVersion);
+        }
+
+        let (content, trailer) = bytes.split_at(bytes.len() - checksum_size);
+        let mut expected = [0u8; 8];
+        expected[..checksum_size].copy_from_slice(trailer);
+        if checksum(content) != u64::from_le_bytes(expected) {
+            return Err(StateError::ChecksumMismatch);
+        }
+
+        Ok(Self {
+            bytes: content,
+            cursor: HEADER_SIZE,
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn read_bytes(&mut self, len: usize) -> &'a [u8] {
+        let result = &self.bytes[self.cursor..self.cursor + len];
+        self.cursor += len;
+        result
+    }
+
+    #[allow(unused)]
+    #[inline(always)]
+    pub(crate) fn read_u8(&mut self) -> u8 {
+        self.read_bytes(1)[0]
+    }
+
+    #[allow(unused)]
+    #[inline(always)]
+    pub(crate) fn read_u16(&mut self) -> u16 {
+        let mut result = [0; 2];
+        result.copy_from_slice(self.read_bytes(2));
+        u16::from_le_bytes(result)
+    }
+
+    #[allow(unused)]
+    #[inline(always)]
+    pub(crate) fn read_u32(&mut self) -> u32 {
+        let mut result = [0; 4];
+        result.copy_from_slice(self.read_bytes(4));
+        u32::from_le_bytes(result)
+    }
+
+    #[allow(unused)]
+    #[inline(always)]
+    pub(crate) fn read_u64(&mut self) -> u64 {
+        let mut result = [0; 8];
+        result.copy_from_slice(self.read_bytes(8));
+        u64::from_le_bytes(result)
+    }
+}
@@ -49,3 +49,37 @@ impl Buffer<*mut u8> {
         }
     }
 }
+
+#[cfg(feature = "std")]
+///Feeds content of `reader` into `update` until its end, reading via `buffer`.
+pub fn update_from_reader<R: std::io::Read>(mut reader: R, buffer: &mut [u8], mut update: impl FnMut(&[u8])) -> std::io::Result<()> {
+    loop {
+        match reader.read(buffer) {
+            Ok(0) => return Ok(()),
+            Ok(size) => update(&buffer[..size]),
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+///Files smaller than this are read, as mapping overhead outweighs its benefit.
+const MMAP_MIN_SIZE: u64 = 16 * 1024;
+
+#[cfg(feature = "mmap")]
+///Hashes file at `path` via `one_shot` over mapped content, falling back to `streaming` when file cannot be mapped.
+pub fn hash_file_mmap<T>(path: &std::path::Path, one_shot: impl FnOnce(&[u8]) -> T, streaming: impl FnOnce(std::fs::File) -> std::io::Result<T>) -> std::io::Result<T> {
+    let file = std::fs::File::open(path)?;
+    let metadata = file.metadata()?;
+    //Only regular files have stable size suitable for mapping
+    if !metadata.is_file() || metadata.len() < MMAP_MIN_SIZE || metadata.len() > usize::MAX as u64 {
+        return streaming(file);
+    }
+
+    //Safety: mapping is read only, but file modified during hashing yields unspecified hash
+    match unsafe { memmap2::Mmap::map(&file) } {
+        Ok(mapped) => Ok(one_shot(&mapped)),
+        Err(_) => streaming(file),
+    }
+}
@@ -9,23 +9,27 @@ use crate::xxh32_common as xxh32;
 use crate::xxh64_common as xxh64;
 use crate::xxh3_common::*;
 use crate::utils::{Buffer, get_unaligned_chunk, get_aligned_chunk_ref};
+use crate::state::{StateReader, StateWriter};
+pub use crate::state::StateError;
+pub use crate::canonical::Xxh128Hash;
 
 // Code is as close to original C implementation as possible
 // It does make it look ugly, but it is fast and easy to update once xxhash gets new version.
 
-#[cfg(all(any(target_feature = "sse2", target_feature = "neon", all(target_family = "wasm", target_feature = "simd128")), not(any(target_feature = "avx2", target_feature = "avx512f"))))]
+#[cfg(all(any(target_feature = "sse2", target_feature = "neon", all(target_family = "wasm", target_feature = "simd128")), not(any(target_feature = "avx2", target_feature = "avx512f", all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))))]
 #[repr(align(16))]
 #[derive(Clone)]
 struct Acc([u64; ACC_NB]);
-#[cfg(all(target_feature = "avx2", not(target_feature = "avx512f")))]
+#[cfg(all(target_feature = "avx2", not(any(target_feature = "avx512f", all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))))]
 #[repr(align(32))]
 #[derive(Clone)]
 struct Acc([u64; ACC_NB]);
-#[cfg(target_feature = "avx512f")]
+//Runtime dispatch may pick AVX512 kernel, so accumulator must be suitable for it
+#[cfg(any(target_feature = "avx512f", all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
 #[repr(align(64))]
 #[derive(Clone)]
 struct Acc([u64; ACC_NB]);
-#[cfg(not(any(target_feature = "avx512f", target_feature = "avx2", target_feature = "neon", all(target_family = "wasm", target_feature = "simd128"), target_feature = "sse2")))]
+#[cfg(not(any(target_feature = "avx512f", target_feature = "avx2", target_feature = "neon", all(target_family = "wasm", target_feature = "simd128"), target_feature = "sse2", all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64")))))]
 #[repr(align(8))]
 #[derive(Clone)]
 struct Acc([u64; ACC_NB]);
@@ -38,23 +42,7 @@ const INITIAL_ACC: Acc = Acc([
 type LongHashFn = fn(&[u8], u64, &[u8]) -> u64;
 type LongHashFn128 = fn(&[u8], u64, &[u8]) -> u128;
 
-#[cfg(all(target_family = "wasm", target_feature = "simd128"))]
-type StripeLanes = [[u8; mem::size_of::<core::arch::wasm32::v128>()]; STRIPE_LEN / mem::size_of::<core::arch::wasm32::v128>()];
-#[cfg(all(target_arch = "x86", target_feature = "avx512f"))]
-type StripeLanes = [[u8; mem::size_of::<core::arch::x86::__m512i>()]; STRIPE_LEN / mem::size_of::<core::arch::x86::__m512i>()];
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
-type StripeLanes = [[u8; mem::size_of::<core::arch::x86_64::__m512i>()]; STRIPE_LEN / mem::size_of::<core::arch::x86_64::__m512i>()];
-#[cfg(all(target_arch = "x86", target_feature = "avx2", not(target_feature = "avx512f")))]
-type StripeLanes = [[u8; mem::size_of::<core::arch::x86::__m256i>()]; STRIPE_LEN / mem::size_of::<core::arch::x86::__m256i>()];
-#[cfg(all(target_arch = "x86_64", target_feature = "avx2", not(target_feature = "avx512f")))]
-type StripeLanes = [[u8; mem::size_of::<core::arch::x86_64::__m256i>()]; STRIPE_LEN / mem::size_of::<core::arch::x86_64::__m256i>()];
-#[cfg(all(target_arch = "x86", target_feature = "sse2", not(any(target_feature = "avx2", target_feature = "avx512f"))))]
-type StripeLanes = [[u8; mem::size_of::<core::arch::x86::__m128i>()]; STRIPE_LEN / mem::size_of::<core::arch::x86::__m128i>()];
-#[cfg(all(target_arch = "x86_64", target_feature = "sse2", not(any(target_feature = "avx2", target_feature = "avx512f"))))]
-type StripeLanes = [[u8; mem::size_of::<core::arch::x86_64::__m128i>()]; STRIPE_LEN / mem::size_of::<core::arch::x86_64::__m128i>()];
-#[cfg(target_feature = "neon")]
-type StripeLanes = [[u8; mem::size_of::<core::arch::aarch64::uint8x16_t>()]; STRIPE_LEN / mem::size_of::<core::arch::aarch64::uint8x16_t>()];
-
+#[derive(Clone, Copy)]
 ///Secret validation wrapper
 pub struct SecretInput<T>(T);
 
@@ -85,7 +73,7 @@ impl<const N: usize> SecretInput<[u8; N]> {
 
 // TODO: replace with [`core::arch::x86::_MM_SHUFFLE`](https://doc.rust-lang.org/core/arch/x86/fn._MM_SHUFFLE.html)
 // when it stabilizes
-#[cfg(any(target_feature = "sse2", target_feature = "avx2", target_feature = "avx512f"))]
+#[cfg(any(target_feature = "sse2", target_feature = "avx2", target_feature = "avx512f", all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
 #[inline]
 const fn _mm_shuffle(z: u32, y: u32, x: u32, w: u32) -> i32 {
     ((z << 6) | (y << 4) | (x << 2) | w) as i32
@@ -138,6 +126,11 @@ fn read_64le_unaligned(data: &[u8], offset: usize) -> u64 {
     u64::from_ne_bytes(*get_aligned_chunk_ref(data, offset)).to_le()
 }
 
+#[inline(always)]
+fn read_128le_unaligned(data: &[u8], offset: usize) -> u128 {
+    u128::from_ne_bytes(*get_aligned_chunk_ref(data, offset)).to_le()
+}
+
 #[inline(always)]
 fn mix_two_accs(acc: &mut Acc, offset: usize, secret: &[[u8; 8]; 2]) -> u64 {
     mul128_fold64(acc.0[offset] ^ u64::from_ne_bytes(secret[0]).to_le(),
@@ -210,7 +203,8 @@ fn custom_default_secret(seed: u64) -> [u8; DEFAULT_SECRET_SIZE] {
 }
 
 #[cfg(all(target_family = "wasm", target_feature = "simd128"))]
-fn accumulate_512_wasm(acc: &mut Acc, input: &StripeLanes, secret: &StripeLanes) {
+#[inline(always)]
+fn accumulate_512_wasm(acc: &mut Acc, input: &[[u8; 16]; 4], secret: &[[u8; 16]; 4]) {
     const LANES: usize = ACC_NB;
 
     use core::arch::wasm32::*;
@@ -266,7 +260,8 @@ macro_rules! vld1q_u8 {
 }
 
 #[cfg(target_feature = "neon")]
-fn accumulate_512_neon(acc: &mut Acc, input: &StripeLanes, secret: &StripeLanes) {
+#[inline(always)]
+fn accumulate_512_neon(acc: &mut Acc, input: &[[u8; 16]; 4], secret: &[[u8; 16]; 4]) {
     //Full Neon version from xxhash source
     const NEON_LANES: usize = ACC_NB;
 
@@ -317,8 +312,9 @@ fn accumulate_512_neon(acc: &mut Acc, input: &StripeLanes, secret: &StripeLanes)
     }
 }
 
-#[cfg(all(target_feature = "sse2", not(any(target_feature = "avx2", target_feature = "avx512f"))))]
-fn accumulate_512_sse2(acc: &mut Acc, input: &StripeLanes, secret: &StripeLanes) {
+#[cfg(any(all(target_feature = "sse2", not(any(target_feature = "avx2", target_feature = "avx512f"))), all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
+#[inline(always)]
+fn accumulate_512_sse2(acc: &mut Acc, input: &[[u8; 16]; 4], secret: &[[u8; 16]; 4]) {
     unsafe {
         #[cfg(target_arch = "x86")]
         use core::arch::x86::*;
@@ -342,8 +338,9 @@ fn accumulate_512_sse2(acc: &mut Acc, input: &StripeLanes, secret: &StripeLanes)
     }
 }
 
-#[cfg(all(target_feature = "avx2", not(target_feature = "avx512f")))]
-fn accumulate_512_avx2(acc: &mut Acc, input: &StripeLanes, secret: &StripeLanes) {
+#[cfg(any(all(target_feature = "avx2", not(target_feature = "avx512f")), all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
+#[inline(always)]
+fn accumulate_512_avx2(acc: &mut Acc, input: &[[u8; 32]; 2], secret: &[[u8; 32]; 2]) {
     unsafe {
         #[cfg(target_arch = "x86")]
         use core::arch::x86::*;
@@ -367,8 +364,9 @@ fn accumulate_512_avx2(acc: &mut Acc, input: &StripeLanes, secret: &StripeLanes)
     }
 }
 
-#[cfg(target_feature = "avx512f")]
-fn accumulate_512_avx512(acc: &mut Acc, input: &StripeLanes, secret: &StripeLanes) {
+#[cfg(any(target_feature = "avx512f", all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
+#[inline(always)]
+fn accumulate_512_avx512(acc: &mut Acc, input: &[[u8; 64]; 1], secret: &[[u8; 64]; 1]) {
     unsafe {
         #[cfg(target_arch = "x86")]
         use core::arch::x86::*;
@@ -392,7 +390,7 @@ fn accumulate_512_avx512(acc: &mut Acc, input: &StripeLanes, secret: &StripeLane
     }
 }
 
-#[cfg(not(any(target_feature = "avx512f", target_feature = "avx2", target_feature = "sse2", target_feature = "neon", all(target_family = "wasm", target_feature = "simd128"))))]
+#[inline(always)]
 fn accumulate_512_scalar(acc: &mut Acc, input: &[[u8; 8]; ACC_NB], secret: &[[u8; 8]; ACC_NB]) {
     for idx in 0..ACC_NB {
         let data_val = u64::from_ne_bytes(input[idx]).to_le();
@@ -404,20 +402,8 @@ fn accumulate_512_scalar(acc: &mut Acc, input: &[[u8; 8]; ACC_NB], secret: &[[u8
 }
 
 #[cfg(all(target_family = "wasm", target_feature = "simd128"))]
-use accumulate_512_wasm as accumulate_512;
-#[cfg(target_feature = "neon")]
-use accumulate_512_neon as accumulate_512;
-#[cfg(all(target_feature = "sse2", not(any(target_feature = "avx2", target_feature = "avx512f"))))]
-use accumulate_512_sse2 as accumulate_512;
-#[cfg(all(target_feature = "avx2", not(target_feature = "avx512f")))]
-use accumulate_512_avx2 as accumulate_512;
-#[cfg(target_feature = "avx512f")]
-use accumulate_512_avx512 as accumulate_512;
-#[cfg(not(any(target_feature = "avx512f", target_feature = "avx2", target_feature = "sse2", target_feature = "neon", all(target_family = "wasm", target_feature = "simd128"))))]
-use accumulate_512_scalar as accumulate_512;
-
-#[cfg(all(target_family = "wasm", target_feature = "simd128"))]
-fn scramble_acc_wasm(acc: &mut Acc, secret: &StripeLanes) {
+#[inline(always)]
+fn scramble_acc_wasm(acc: &mut Acc, secret: &[[u8; 16]; 4]) {
     use core::arch::wasm32::*;
 
     let xacc = acc.0.as_mut_ptr() as *mut v128;
@@ -436,7 +422,8 @@ fn scramble_acc_wasm(acc: &mut Acc, secret: &StripeLanes) {
 }
 
 #[cfg(target_feature = "neon")]
-fn scramble_acc_neon(acc: &mut Acc, secret: &StripeLanes) {
+#[inline(always)]
+fn scramble_acc_neon(acc: &mut Acc, secret: &[[u8; 16]; 4]) {
     //Full Neon version from xxhash source
     unsafe {
         #[cfg(target_arch = "arm")]
@@ -468,8 +455,9 @@ fn scramble_acc_neon(acc: &mut Acc, secret: &StripeLanes) {
     }
 }
 
-#[cfg(all(target_feature = "sse2", not(any(target_feature = "avx2", target_feature = "avx512f"))))]
-fn scramble_acc_sse2(acc: &mut Acc, secret: &StripeLanes) {
+#[cfg(any(all(target_feature = "sse2", not(any(target_feature = "avx2", target_feature = "avx512f"))), all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
+#[inline(always)]
+fn scramble_acc_sse2(acc: &mut Acc, secret: &[[u8; 16]; 4]) {
     unsafe {
         #[cfg(target_arch = "x86")]
         use core::arch::x86::*;
@@ -495,8 +483,9 @@ fn scramble_acc_sse2(acc: &mut Acc, secret: &StripeLanes) {
     }
 }
 
-#[cfg(all(target_feature = "avx2", not(target_feature = "avx512f")))]
-fn scramble_acc_avx2(acc: &mut Acc, secret: &StripeLanes) {
+#[cfg(any(all(target_feature = "avx2", not(target_feature = "avx512f")), all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
+#[inline(always)]
+fn scramble_acc_avx2(acc: &mut Acc, secret: &[[u8; 32]; 2]) {
     unsafe {
         #[cfg(target_arch = "x86")]
         use core::arch::x86::*;
@@ -522,8 +511,9 @@ fn scramble_acc_avx2(acc: &mut Acc, secret: &StripeLanes) {
     }
 }
 
-#[cfg(target_feature = "avx512f")]
-fn scramble_acc_avx512(acc: &mut Acc, secret: &StripeLanes) {
+#[cfg(any(target_feature = "avx512f", all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
+#[inline(always)]
+fn scramble_acc_avx512(acc: &mut Acc, secret: &[[u8; 64]; 1]) {
     unsafe {
         #[cfg(target_arch = "x86")]
         use core::arch::x86::*;
@@ -548,7 +538,7 @@ fn scramble_acc_avx512(acc: &mut Acc, secret: &StripeLanes) {
     }
 }
 
-#[cfg(not(any(target_feature = "avx512f", target_feature = "avx2", target_feature = "sse2", target_feature = "neon", all(target_family = "wasm", target_feature = "simd128"))))]
+#[inline(always)]
 fn scramble_acc_scalar(acc: &mut Acc, secret: &[[u8; 8]; ACC_NB]) {
     for idx in 0..secret.len() {
         let key = u64::from_ne_bytes(secret[idx]).to_le();
@@ -558,33 +548,286 @@ fn scramble_acc_scalar(acc: &mut Acc, secret: &[[u8; 8]; ACC_NB]) {
     }
 }
 
+//Kernel is implemented by each SIMD flavour, allowing hashing loops to be instantiated
+//for particular set of instructions.
+//
+//Implementations assume instructions are supported, which is guaranteed either by compile
+//time flags or by runtime dispatch.
+trait Kernel {
+    type Lanes: Copy;
+
+    fn accumulate_512(acc: &mut Acc, input: &Self::Lanes, secret: &Self::Lanes);
+    fn scramble_acc(acc: &mut Acc, secret: &Self::Lanes);
+}
+
+macro_rules! impl_kernel {
+    ($kernel:ident($lanes:ty) => $accumulate_512:ident, $scramble_acc:ident) => {
+        impl Kernel for $kernel {
+            type Lanes = $lanes;
+
+            #[inline(always)]
+            fn accumulate_512(acc: &mut Acc, input: &Self::Lanes, secret: &Self::Lanes) {
+                $accumulate_512(acc, input, secret)
+            }
+
+            #[inline(always)]
+            fn scramble_acc(acc: &mut Acc, secret: &Self::Lanes) {
+                $scramble_acc(acc, secret)
+            }
+        }
+    }
+}
+
 #[cfg(all(target_family = "wasm", target_feature = "simd128"))]
-use scramble_acc_wasm as scramble_acc;
+struct Wasm;
+#[cfg(all(target_family = "wasm", target_feature = "simd128"))]
+impl_kernel!(Wasm([[u8; 16]; 4]) => accumulate_512_wasm, scramble_acc_wasm);
 
 #[cfg(target_feature = "neon")]
-use scramble_acc_neon as scramble_acc;
+struct Neon;
+#[cfg(target_feature = "neon")]
+impl_kernel!(Neon([[u8; 16]; 4]) => accumulate_512_neon, scramble_acc_neon);
 
-#[cfg(all(target_feature = "sse2", not(any(target_feature = "avx2", target_feature = "avx512f"))))]
-use scramble_acc_sse2 as scramble_acc;
+#[cfg(any(all(target_feature = "sse2", not(any(target_feature = "avx2", target_feature = "avx512f"))), all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
+struct Sse2;
+#[cfg(any(all(target_feature = "sse2", not(any(target_feature = "avx2", target_feature = "avx512f"))), all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
+impl_kernel!(Sse2([[u8; 16]; 4]) => accumulate_512_sse2, scramble_acc_sse2);
 
-#[cfg(all(target_feature = "avx2", not(target_feature = "avx512f")))]
-use scramble_acc_avx2 as scramble_acc;
+#[cfg(any(all(target_feature = "avx2", not(target_feature = "avx512f")), all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
+struct Avx2;
+#[cfg(any(all(target_feature = "avx2", not(target_feature = "avx512f")), all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
+impl_kernel!(Avx2([[u8; 32]; 2]) => accumulate_512_avx2, scramble_acc_avx2);
 
-#[cfg(target_feature = "avx512f")]
-use scramble_acc_avx512 as scramble_acc;
+#[cfg(any(target_feature = "avx512f", all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
+struct Avx512;
+#[cfg(any(target_feature = "avx512f", all(feature = "runtime_dispatch", any(target_arch = "x86", target_arch = "x86_64"))))]
+impl_kernel!(Avx512([[u8; 64]; 1]) => accumulate_512_avx512, scramble_acc_avx512);
+
+//Always available as reference for other kernels
+struct Scalar;
+impl_kernel!(Scalar([[u8; 8]; ACC_NB]) => accumulate_512_scalar, scramble_acc_scalar);
 
+//Kernel selected at compile time
+#[cfg(all(target_family = "wasm", target_feature = "simd128"))]
+type NativeKernel = Wasm;
+#[cfg(target_feature = "neon")]
+type NativeKernel = Neon;
+#[cfg(all(target_feature = "sse2", not(any(target_feature = "avx2", target_feature = "avx512f"))))]
+type NativeKernel = Sse2;
+#[cfg(all(target_feature = "avx2", not(target_feature = "avx512f")))]
+type NativeKernel = Avx2;
+#[cfg(target_feature = "avx512f")]
+type NativeKernel = Avx512;
 #[cfg(not(any(target_feature = "avx512f", target_feature = "avx2", target_feature = "sse2", target_feature = "neon", all(target_family = "wasm", target_feature = "simd128"))))]
-use scramble_acc_scalar as scramble_acc;
+type NativeKernel = Scalar;
+
+#[cfg(feature = "runtime_dispatch")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Kernel used to process inputs longer than `MID_SIZE_MAX`
+///
+///Available only with `runtime_dispatch` feature, which selects the fastest kernel supported by CPU
+///on first use of long input.
+pub enum Xxh3Backend {
+    ///Portable implementation without SIMD.
+    Scalar,
+    ///SSE2 kernel.
+    Sse2,
+    ///AVX2 kernel.
+    Avx2,
+    ///AVX512 kernel.
+    Avx512,
+    ///Neon kernel, available only when enabled at compile time.
+    Neon,
+    ///Wasm SIMD128 kernel, available only when enabled at compile time.
+    Simd128,
+}
+
+#[cfg(feature = "runtime_dispatch")]
+impl Xxh3Backend {
+    #[inline]
+    ///Returns the fastest backend supported by current CPU.
+    pub fn detect() -> Self {
+        dispatch::detect().backend
+    }
+
+    #[inline]
+    ///Returns whether backend can be used on current CPU.
+    pub fn is_available(self) -> bool {
+        dispatch::find(self).is_some()
+    }
+
+    #[inline]
+    ///Returns backend currently used by all `xxh3` functions.
+    pub fn current() -> Self {
+        dispatch::kernels().backend
+    }
+
+    #[inline]
+    ///Forces all `xxh3` functions to use this backend.
+    ///
+    ///Intended for testing and benchmarking as every backend produces the same output.
+    ///
+    ///Returns `false` if backend is not available on current CPU, leaving selection unchanged.
+    pub fn force(self) -> bool {
+        dispatch::force(self)
+    }
+}
+
+#[cfg(feature = "runtime_dispatch")]
+mod dispatch {
+    use core::ptr;
+    use core::sync::atomic::{AtomicPtr, Ordering};
+
+    use super::*;
+
+    type ConsumeStripesFn = unsafe fn(&mut Acc, usize, usize, *const u8, &[u8]) -> usize;
+    type DigestInternalFn = unsafe fn(&mut Acc, usize, &[u8], &[mem::MaybeUninit<u8>], &[u8]);
+
+    pub(super) struct Kernels {
+        pub(super) backend: Xxh3Backend,
+        pub(super) long_64: unsafe fn(&[u8], &[u8]) -> u64,
+        pub(super) long_128: unsafe fn(&[u8], &[u8]) -> u128,
+        pub(super) consume_stripes: ConsumeStripesFn,
+        pub(super) digest_internal: DigestInternalFn,
+    }
+
+    //Instantiates hashing loops for kernel, optionally enabling target feature for them.
+    macro_rules! kernels {
+        ($kernel:ty => $backend:expr $(, $feature:literal)?) => {{
+            $(#[target_feature(enable = $feature)])?
+            unsafe fn long_64(input: &[u8], secret: &[u8]) -> u64 {
+                xxh3_64_long_kernel::<$kernel>(input, secret)
+            }
+
+            $(#[target_feature(enable = $feature)])?
+            unsafe fn long_128(input: &[u8], secret: &[u8]) -> u128 {
+                xxh3_128_long_kernel::<$kernel>(input, secret)
+            }
+
+            $(#[target_feature(enable = $feature)])?
+            unsafe fn consume_stripes(acc: &mut Acc, nb_stripes: usize, nb_stripes_acc: usize, input: *const u8, secret: &[u8]) -> usize {
+                xxh3_stateful_consume_stripes_kernel::<$kernel>(acc, nb_stripes, nb_stripes_acc, input, secret)
+            }
+
+            $(#[target_feature(enable = $feature)])?
+            unsafe fn digest_internal(acc: &mut Acc, nb_stripes_acc: usize, buffer: &[u8], old_buffer: &[mem::MaybeUninit<u8>], secret: &[u8]) {
+                xxh3_stateful_digest_internal_kernel::<$kernel>(acc, nb_stripes_acc, buffer, old_buffer, secret)
+            }
+
+            Kernels {
+                backend: $backend,
+                long_64,
+                long_128,
+                consume_stripes,
+                digest_internal,
+            }
+        }}
+    }
+
+    #[cfg(all(target_family = "wasm", target_feature = "simd128"))]
+    const NATIVE_BACKEND: Xxh3Backend = Xxh3Backend::Simd128;
+    #[cfg(target_feature = "neon")]
+    const NATIVE_BACKEND: Xxh3Backend = Xxh3Backend::Neon;
+    #[cfg(all(target_feature = "sse2", not(any(target_feature = "avx2", target_feature = "avx512f"))))]
+    const NATIVE_BACKEND: Xxh3Backend = Xxh3Backend::Sse2;
+    #[cfg(all(target_feature = "avx2", not(target_feature = "avx512f")))]
+    const NATIVE_BACKEND: Xxh3Backend = Xxh3Backend::Avx2;
+    #[cfg(target_feature = "avx512f")]
+    const NATIVE_BACKEND: Xxh3Backend = Xxh3Backend::Avx512;
+    #[cfg(not(any(target_feature = "avx512f", target_feature = "avx2", target_feature = "sse2", target_feature = "neon", all(target_family = "wasm", target_feature = "simd128"))))]
+    const NATIVE_BACKEND: Xxh3Backend = Xxh3Backend::Scalar;
+
+    static NATIVE: Kernels = kernels!(NativeKernel => NATIVE_BACKEND);
+    static SCALAR: Kernels = kernels!(Scalar => Xxh3Backend::Scalar);
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    static SSE2: Kernels = kernels!(Sse2 => Xxh3Backend::Sse2, "sse2");
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    static AVX2: Kernels = kernels!(Avx2 => Xxh3Backend::Avx2, "avx2");
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    static AVX512: Kernels = kernels!(Avx512 => Xxh3Backend::Avx512, "avx512f");
+
+    static SELECTED: AtomicPtr<Kernels> = AtomicPtr::new(ptr::null_mut());
+
+    pub(super) fn detect() -> &'static Kernels {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if std::is_x86_feature_detected!("avx512f") {
+                return &AVX512;
+            } else if std::is_x86_feature_detected!("avx2") {
+                return &AVX2;
+            } else if std::is_x86_feature_detected!("sse2") {
+                return &SSE2;
+            }
+        }
+
+        &NATIVE
+    }
+
+    pub(super) fn find(backend: Xxh3Backend) -> Option<&'static Kernels> {
+        if backend == Xxh3Backend::Scalar {
+            return Some(&SCALAR);
+        } else if backend == NATIVE_BACKEND {
+            return Some(&NATIVE);
+        }
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            let (kernels, available) = match backend {
+                Xxh3Backend::Sse2 => (&SSE2, std::is_x86_feature_detected!("sse2")),
+                Xxh3Backend::Avx2 => (&AVX2, std::is_x86_feature_detected!("avx2")),
+                Xxh3Backend::Avx512 => (&AVX512, std::is_x86_feature_detected!("avx512f")),
+                _ => return None,
+            };
+
+            if available {
+                return Some(kernels);
+            }
+        }
+
+        None
+    }
+
+    pub(super) fn force(backend: Xxh3Backend) -> bool {
+        match find(backend) {
+            Some(kernels) => {
+                SELECTED.store(kernels as *const Kernels as *mut Kernels, Ordering::Relaxed);
+                true
+            },
+            None => false,
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn select() -> &'static Kernels {
+        let kernels = detect();
+        SELECTED.store(kernels as *const Kernels as *mut Kernels, Ordering::Relaxed);
+        kernels
+    }
+
+    #[inline(always)]
+    pub(super) fn kernels() -> &'static Kernels {
+        let kernels = SELECTED.load(Ordering::Relaxed);
+        if kernels.is_null() {
+            select()
+        } else {
+            //Only ever points to one of statics above
+            unsafe {
+                &*kernels
+            }
+        }
+    }
+}
 
 #[inline(always)]
-fn accumulate_loop(acc: &mut Acc, input: *const u8, secret: *const u8, nb_stripes: usize) {
+fn accumulate_loop<K: Kernel>(acc: &mut Acc, input: *const u8, secret: *const u8, nb_stripes: usize) {
     for idx in 0..nb_stripes {
         unsafe {
             let input = input.add(idx * STRIPE_LEN);
             //Miri complains about it for dumb reason so for not turn off prefetch
             //_mm_prefetch(input as _, 320);
 
-            accumulate_512(acc,
+            K::accumulate_512(acc,
                 &*(input as *const _),
                 &*(secret.add(idx * SECRET_CONSUME_RATE) as *const _)
             );
@@ -592,15 +835,15 @@ fn accumulate_loop(acc: &mut Acc, input: *const u8, secret: *const u8, nb_stripe
     }
 }
 
-#[inline]
-fn hash_long_internal_loop(acc: &mut Acc, input: &[u8], secret: &[u8]) {
+#[inline(always)]
+fn hash_long_internal_loop<K: Kernel>(acc: &mut Acc, input: &[u8], secret: &[u8]) {
     let nb_stripes = (secret.len() - STRIPE_LEN) / SECRET_CONSUME_RATE;
     let block_len = STRIPE_LEN * nb_stripes;
     let nb_blocks = (input.len() - 1) / block_len;
 
     for idx in 0..nb_blocks {
-        accumulate_loop(acc, slice_offset_ptr!(input, idx * block_len), secret.as_ptr(), nb_stripes);
-        scramble_acc(acc, get_aligned_chunk_ref(secret, secret.len() - STRIPE_LEN));
+        accumulate_loop::<K>(acc, slice_offset_ptr!(input, idx * block_len), secret.as_ptr(), nb_stripes);
+        K::scramble_acc(acc, get_aligned_chunk_ref(secret, secret.len() - STRIPE_LEN));
     }
 
     //last partial block
@@ -608,10 +851,10 @@ fn hash_long_internal_loop(acc: &mut Acc, input: &[u8], secret: &[u8]) {
 
     let nb_stripes = ((input.len() - 1) - (block_len * nb_blocks)) / STRIPE_LEN;
     debug_assert!(nb_stripes <= (secret.len() / SECRET_CONSUME_RATE));
-    accumulate_loop(acc, slice_offset_ptr!(input, nb_blocks * block_len), secret.as_ptr(), nb_stripes);
+    accumulate_loop::<K>(acc, slice_offset_ptr!(input, nb_blocks * block_len), secret.as_ptr(), nb_stripes);
 
     //last stripe
-    accumulate_512(acc, get_aligned_chunk_ref(input, input.len() - STRIPE_LEN), get_aligned_chunk_ref(secret, secret.len() - STRIPE_LEN - SECRET_LASTACC_START));
+    K::accumulate_512(acc, get_aligned_chunk_ref(input, input.len() - STRIPE_LEN), get_aligned_chunk_ref(secret, secret.len() - STRIPE_LEN - SECRET_LASTACC_START));
 }
 
 #[inline(always)]
@@ -646,6 +889,11 @@ fn xxh3_64_4to8(input: &[u8], mut seed: u64, secret: &[u8]) -> u64 {
 
 #[inline(always)]
 fn xxh3_64_9to16(input: &[u8], seed: u64, secret: &[u8]) -> u64 {
+    avalanche(xxh3_64_9to16_acc(input, seed, secret))
+}
+
+#[inline(always)]
+fn xxh3_64_9to16_acc(input: &[u8], seed: u64, secret: &[u8]) -> u64 {
     debug_assert!(input.len() >= 9 && input.len() <= 16);
 
     let flip1 = (read_64le_unaligned(secret, 24) ^ read_64le_unaligned(secret, 32)).wrapping_add(seed);
@@ -654,11 +902,9 @@ fn xxh3_64_9to16(input: &[u8], seed: u64, secret: &[u8]) -> u64 {
     let input_lo = read_64le_unaligned(input, 0) ^ flip1;
     let input_hi = read_64le_unaligned(input, input.len() - 8) ^ flip2;
 
-    let acc = (input.len() as u64).wrapping_add(input_lo.swap_bytes())
-                                  .wrapping_add(input_hi)
-                                  .wrapping_add(mul128_fold64(input_lo, input_hi));
-
-    avalanche(acc)
+    (input.len() as u64).wrapping_add(input_lo.swap_bytes())
+                        .wrapping_add(input_hi)
+                        .wrapping_add(mul128_fold64(input_lo, input_hi))
 }
 
 #[inline(always)]
@@ -676,6 +922,11 @@ fn xxh3_64_0to16(input: &[u8], seed: u64, secret: &[u8]) -> u64 {
 
 #[inline(always)]
 fn xxh3_64_7to128(input: &[u8], seed: u64, secret: &[u8]) -> u64 {
+    avalanche(xxh3_64_7to128_acc(input, seed, secret))
+}
+
+#[inline(always)]
+fn xxh3_64_7to128_acc(input: &[u8], seed: u64, secret: &[u8]) -> u64 {
     let mut acc = (input.len() as u64).wrapping_mul(xxh64::PRIME_1);
 
     if input.len() > 32 {
@@ -722,13 +973,11 @@ fn xxh3_64_7to128(input: &[u8], seed: u64, secret: &[u8]) -> u64 {
         get_aligned_chunk_ref(secret, 0),
         seed
     ));
-    acc = acc.wrapping_add(mix16_b(
+    acc.wrapping_add(mix16_b(
         get_aligned_chunk_ref(input, input.len() - 16),
         get_aligned_chunk_ref(secret, 16),
         seed
-    ));
-
-    avalanche(acc)
+    ))
 }
 
 #[inline(never)]
@@ -791,14 +1040,26 @@ fn xxh3_64_internal(input: &[u8], seed: u64, secret: &[u8], long_hash_fn: LongHa
 }
 
 #[inline(always)]
-fn xxh3_64_long_impl(input: &[u8], secret: &[u8]) -> u64 {
+fn xxh3_64_long_kernel<K: Kernel>(input: &[u8], secret: &[u8]) -> u64 {
     let mut acc = INITIAL_ACC;
 
-    hash_long_internal_loop(&mut acc, input, secret);
+    hash_long_internal_loop::<K>(&mut acc, input, secret);
 
     merge_accs(&mut acc, get_aligned_chunk_ref(secret, SECRET_MERGEACCS_START), (input.len() as u64).wrapping_mul(xxh64::PRIME_1))
 }
 
+#[inline(always)]
+fn xxh3_64_long_impl(input: &[u8], secret: &[u8]) -> u64 {
+    #[cfg(not(feature = "runtime_dispatch"))]
+    {
+        xxh3_64_long_kernel::<NativeKernel>(input, secret)
+    }
+    #[cfg(feature = "runtime_dispatch")]
+    unsafe {
+        (dispatch::kernels().long_64)(input, secret)
+    }
+}
+
 #[inline(never)]
 fn xxh3_64_long_with_seed(input: &[u8], seed: u64, _secret: &[u8]) -> u64 {
     match seed {
@@ -850,30 +1111,264 @@ pub fn xxh3_64_with_secret_input(input: &[u8], secret: &SecretInput<impl AsRef<[
     xxh3_64_internal(input, 0, secret.0.as_ref(), xxh3_64_long_with_secret)
 }
 
+#[inline]
+///Returns 64bit hash for provided input using both custom secret and seed.
+///
+///Seed is used for inputs up to `MID_SIZE_MAX`, while secret is used for larger inputs,
+///which avoids cost of deriving secret from seed as in [xxh3_64_with_seed].
+///
+///This function panics if `secret` doesn't fit minimum required secret size.
+pub fn xxh3_64_with_secret_and_seed(input: &[u8], secret: &[u8], seed: u64) -> u64 {
+    assert!(secret.len() >= SECRET_SIZE_MIN);
+    if input.len() <= MID_SIZE_MAX {
+        xxh3_64_internal(input, seed, &DEFAULT_SECRET, xxh3_64_long_with_seed)
+    } else {
+        xxh3_64_long_with_secret(input, seed, secret)
+    }
+}
+
+#[inline]
+///Returns 64bit hash of `value`, same as `xxh3_64(&value.to_le_bytes())`.
+pub fn hash_u32(value: u32) -> u64 {
+    hash_u32_with_seed(value, 0)
+}
+
+#[inline]
+///Returns 64bit hash of `value` using seed, same as `xxh3_64_with_seed(&value.to_le_bytes(), seed)`.
+pub fn hash_u32_with_seed(value: u32, mut seed: u64) -> u64 {
+    //xxh3_64_4to8 with both halves of input being `value`
+    seed ^= ((seed as u32).swap_bytes() as u64) << 32;
+
+    let flip = (read_64le_unaligned(&DEFAULT_SECRET, 8) ^ read_64le_unaligned(&DEFAULT_SECRET, 16)).wrapping_sub(seed);
+    let input64 = (value as u64).wrapping_add((value as u64) << 32);
+    strong_avalanche(input64 ^ flip, 4)
+}
+
+#[inline]
+///Returns 64bit hash of `value`, same as `xxh3_64(&value.to_le_bytes())`.
+pub fn hash_u64(value: u64) -> u64 {
+    hash_u64_with_seed(value, 0)
+}
+
+#[inline]
+///Returns 64bit hash of `value` using seed, same as `xxh3_64_with_seed(&value.to_le_bytes(), seed)`.
+pub fn hash_u64_with_seed(value: u64, mut seed: u64) -> u64 {
+    //xxh3_64_4to8 with halves of input swapped
+    seed ^= ((seed as u32).swap_bytes() as u64) << 32;
+
+    let flip = (read_64le_unaligned(&DEFAULT_SECRET, 8) ^ read_64le_unaligned(&DEFAULT_SECRET, 16)).wrapping_sub(seed);
+    strong_avalanche(value.rotate_left(32) ^ flip, 8)
+}
+
+#[inline]
+///Returns 64bit hash of `value`, same as `xxh3_64(&value.to_le_bytes())`.
+pub fn hash_u128(value: u128) -> u64 {
+    hash_u128_with_seed(value, 0)
+}
+
+#[inline]
+///Returns 64bit hash of `value` using seed, same as `xxh3_64_with_seed(&value.to_le_bytes(), seed)`.
+pub fn hash_u128_with_seed(value: u128, seed: u64) -> u64 {
+    //xxh3_64_9to16 with 16 bytes input
+    let flip1 = (read_64le_unaligned(&DEFAULT_SECRET, 24) ^ read_64le_unaligned(&DEFAULT_SECRET, 32)).wrapping_add(seed);
+    let flip2 = (read_64le_unaligned(&DEFAULT_SECRET, 40) ^ read_64le_unaligned(&DEFAULT_SECRET, 48)).wrapping_sub(seed);
+
+    let input_lo = (value as u64) ^ flip1;
+    let input_hi = ((value >> 64) as u64) ^ flip2;
+
+    let acc = 16u64.wrapping_add(input_lo.swap_bytes())
+                   .wrapping_add(input_hi)
+                   .wrapping_add(mul128_fold64(input_lo, input_hi));
+
+    avalanche(acc)
+}
+
+const BATCH_LANES: usize = 4;
+
+#[inline(always)]
+fn xxh3_64_4to8_keyed(input: &[u8], flip: u64) -> u64 {
+    let input1 = read_32le_unaligned(input, 0);
+    let input2 = read_32le_unaligned(input, input.len() - 4);
+    (input2 as u64).wrapping_add((input1 as u64) << 32) ^ flip
+}
+
+#[inline(always)]
+//xxh3_64_9to16 and xxh3_64_7to128 for 9 to 32 bytes input, selecting between them without branching on length.
+//Both read first and last 8 bytes, while second and second to last 8 bytes are only used by the latter.
+fn xxh3_64_9to32_lane(input: &[u8]) -> u64 {
+    debug_assert!(input.len() >= 9 && input.len() <= 32);
+    let len = input.len() as u64;
+    let is_long = input.len() > 16;
+    let secret = &DEFAULT_SECRET;
+
+    let head = read_64le_unaligned(input, 0);
+    let tail = read_64le_unaligned(input, input.len() - 8);
+    //Overlaps with head and tail for up to 16 bytes input, where its result is discarded
+    let second = read_64le_unaligned(input, if is_long { 8 } else { 0 });
+    let second_tail = read_64le_unaligned(input, if is_long { input.len() - 16 } else { 0 });
+
+    let (lo_key, hi, acc) = if is_long {
+        (read_64le_unaligned(secret, 0),
+         second ^ read_64le_unaligned(secret, 8),
+         len.wrapping_mul(xxh64::PRIME_1).wrapping_add(mul128_fold64(second_tail ^ read_64le_unaligned(secret, 16), tail ^ read_64le_unaligned(secret, 24))))
+    } else {
+        (read_64le_unaligned(secret, 24) ^ read_64le_unaligned(secret, 32),
+         tail ^ read_64le_unaligned(secret, 40) ^ read_64le_unaligned(secret, 48),
+         len)
+    };
+    let lo = head ^ lo_key;
+    let acc = if is_long { acc } else { acc.wrapping_add(lo.swap_bytes()).wrapping_add(hi) };
+
+    avalanche(acc.wrapping_add(mul128_fold64(lo, hi)))
+}
+
+///Computes [xxh3_64] of each key into corresponding element of `out`.
+///
+///Keys are processed in groups of several keys, hashed by straight-line code interleaving keys of the group.
+///Group of keys from 4 to 8, 9 to 32 or 17 to 128 bytes is hashed without branching on length of every key,
+///where 9 to 32 bytes keys may mix lengths below and above 16 bytes. Other groups are hashed key by key.
+///
+///This function panics if `keys` and `out` have different length.
+pub fn xxh3_64_batch(keys: &[&[u8]], out: &mut [u64]) {
+    assert_eq!(keys.len(), out.len(), "keys and out must have the same length");
+
+    let flip = read_64le_unaligned(&DEFAULT_SECRET, 8) ^ read_64le_unaligned(&DEFAULT_SECRET, 16);
+
+    let mut keys = keys.chunks_exact(BATCH_LANES);
+    let mut out = out.chunks_exact_mut(BATCH_LANES);
+    for (keys, out) in (&mut keys).zip(&mut out) {
+        let min_len = keys.iter().map(|key| key.len()).min().unwrap_or(0);
+        let max_len = keys.iter().map(|key| key.len()).max().unwrap_or(0);
+
+        if min_len >= 4 && max_len <= 8 {
+            for lane in 0..BATCH_LANES {
+                out[lane] = strong_avalanche(xxh3_64_4to8_keyed(keys[lane], flip), keys[lane].len() as u64);
+            }
+        } else if min_len > 8 && max_len <= 16 {
+            for lane in 0..BATCH_LANES {
+                out[lane] = avalanche(xxh3_64_9to16_acc(keys[lane], 0, &DEFAULT_SECRET));
+            }
+        } else if min_len > 8 && max_len <= 32 {
+            for lane in 0..BATCH_LANES {
+                out[lane] = xxh3_64_9to32_lane(keys[lane]);
+            }
+        } else if min_len > 16 && max_len <= 128 {
+            for lane in 0..BATCH_LANES {
+                out[lane] = avalanche(xxh3_64_7to128_acc(keys[lane], 0, &DEFAULT_SECRET));
+            }
+        } else {
+            for lane in 0..BATCH_LANES {
+                out[lane] = xxh3_64_internal(keys[lane], 0, &DEFAULT_SECRET, xxh3_64_long_default);
+            }
+        }
+    }
+
+    for (key, out) in keys.remainder().iter().zip(out.into_remainder()) {
+        *out = xxh3_64(key);
+    }
+}
+
+#[cfg(target_feature = "avx2")]
+#[inline(always)]
+//hash_u64 of 4 keys at once, 64bit multiplication is assembled out of 32bit products
+fn xxh3_64_u64_lanes(keys: &[u64], out: &mut [u64], flip: u64) {
+    unsafe {
+        #[cfg(target_arch = "x86")]
+        use core::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use core::arch::x86_64::*;
+
+        #[inline(always)]
+        unsafe fn mul_prime(value: __m256i) -> __m256i {
+            let prime = _mm256_set1_epi64x(STRONG_AVALANCHE_PRIME as i64);
+            let prime_hi = _mm256_set1_epi64x((STRONG_AVALANCHE_PRIME >> 32) as i64);
+            let low = _mm256_mul_epu32(value, prime);
+            let cross = _mm256_add_epi64(_mm256_mul_epu32(_mm256_srli_epi64(value, 32), prime), _mm256_mul_epu32(value, prime_hi));
+            _mm256_add_epi64(low, _mm256_slli_epi64(cross, 32))
+        }
+
+        let value = _mm256_loadu_si256(keys.as_ptr() as _);
+        //rotate_left(32) of key is swap of 32bit halves
+        let mut value = _mm256_xor_si256(_mm256_shuffle_epi32(value, _mm_shuffle(2, 3, 0, 1)), _mm256_set1_epi64x(flip as i64));
+
+        let rotl49 = _mm256_or_si256(_mm256_slli_epi64(value, 49), _mm256_srli_epi64(value, 15));
+        let rotl24 = _mm256_or_si256(_mm256_slli_epi64(value, 24), _mm256_srli_epi64(value, 40));
+        value = _mm256_xor_si256(value, _mm256_xor_si256(rotl49, rotl24));
+        value = mul_prime(value);
+        value = _mm256_xor_si256(value, _mm256_add_epi64(_mm256_srli_epi64(value, 35), _mm256_set1_epi64x(8)));
+        value = mul_prime(value);
+        value = _mm256_xor_si256(value, _mm256_srli_epi64(value, 28));
+
+        _mm256_storeu_si256(out.as_mut_ptr() as _, value);
+    }
+}
+
+#[cfg(not(target_feature = "avx2"))]
+#[inline(always)]
+fn xxh3_64_u64_lanes(keys: &[u64], out: &mut [u64], flip: u64) {
+    for lane in 0..BATCH_LANES {
+        out[lane] = strong_avalanche(keys[lane].rotate_left(32) ^ flip, 8);
+    }
+}
+
+///Computes [xxh3_64] of little-endian bytes of each key into corresponding element of `out`.
+///
+///Result is the same as `xxh3_64(&key.to_le_bytes())`.
+///
+///This function panics if `keys` and `out` have different length.
+pub fn xxh3_64_u64_batch(keys: &[u64], out: &mut [u64]) {
+    assert_eq!(keys.len(), out.len(), "keys and out must have the same length");
+
+    let flip = read_64le_unaligned(&DEFAULT_SECRET, 8) ^ read_64le_unaligned(&DEFAULT_SECRET, 16);
+
+    let mut keys = keys.chunks_exact(BATCH_LANES);
+    let mut out = out.chunks_exact_mut(BATCH_LANES);
+    for (keys, out) in (&mut keys).zip(&mut out) {
+        xxh3_64_u64_lanes(keys, out, flip);
+    }
+
+    for (key, out) in keys.remainder().iter().zip(out.into_remainder()) {
+        *out = hash_u64(*key);
+    }
+}
+
 const INTERNAL_BUFFER_SIZE: usize = 256;
-const STRIPES_PER_BLOCK: usize = (DEFAULT_SECRET_SIZE - STRIPE_LEN) / SECRET_CONSUME_RATE;
 
 #[derive(Clone)]
 #[repr(align(64))]
 struct Aligned64<T>(T);
 
-#[inline]
+#[inline(always)]
 //Internal function shared between Xxh3 and Xxh3Default
-fn xxh3_stateful_consume_stripes(acc: &mut Acc, nb_stripes: usize, nb_stripes_acc: usize, input: *const u8, secret: &[u8; DEFAULT_SECRET_SIZE]) -> usize {
-    if (STRIPES_PER_BLOCK - nb_stripes_acc) <= nb_stripes {
-        let stripes_to_end = STRIPES_PER_BLOCK - nb_stripes_acc;
+fn xxh3_stateful_consume_stripes_kernel<K: Kernel>(acc: &mut Acc, nb_stripes: usize, nb_stripes_acc: usize, input: *const u8, secret: &[u8]) -> usize {
+    let stripes_per_block = (secret.len() - STRIPE_LEN) / SECRET_CONSUME_RATE;
+
+    if (stripes_per_block - nb_stripes_acc) <= nb_stripes {
+        let stripes_to_end = stripes_per_block - nb_stripes_acc;
         let stripes_after_end = nb_stripes - stripes_to_end;
 
-        accumulate_loop(acc, input, slice_offset_ptr!(secret, nb_stripes_acc * SECRET_CONSUME_RATE), stripes_to_end);
-        scramble_acc(acc, get_aligned_chunk_ref(secret, DEFAULT_SECRET_SIZE - STRIPE_LEN));
-        accumulate_loop(acc, unsafe { input.add(stripes_to_end * STRIPE_LEN) }, secret.as_ptr(), stripes_after_end);
+        accumulate_loop::<K>(acc, input, slice_offset_ptr!(secret, nb_stripes_acc * SECRET_CONSUME_RATE), stripes_to_end);
+        K::scramble_acc(acc, get_aligned_chunk_ref(secret, secret.len() - STRIPE_LEN));
+        accumulate_loop::<K>(acc, unsafe { input.add(stripes_to_end * STRIPE_LEN) }, secret.as_ptr(), stripes_after_end);
         stripes_after_end
     } else {
-        accumulate_loop(acc, input, slice_offset_ptr!(secret, nb_stripes_acc * SECRET_CONSUME_RATE), nb_stripes);
+        accumulate_loop::<K>(acc, input, slice_offset_ptr!(secret, nb_stripes_acc * SECRET_CONSUME_RATE), nb_stripes);
         nb_stripes_acc.wrapping_add(nb_stripes)
     }
 }
 
+#[inline]
+fn xxh3_stateful_consume_stripes(acc: &mut Acc, nb_stripes: usize, nb_stripes_acc: usize, input: *const u8, secret: &[u8]) -> usize {
+    #[cfg(not(feature = "runtime_dispatch"))]
+    {
+        xxh3_stateful_consume_stripes_kernel::<NativeKernel>(acc, nb_stripes, nb_stripes_acc, input, secret)
+    }
+    #[cfg(feature = "runtime_dispatch")]
+    unsafe {
+        (dispatch::kernels().consume_stripes)(acc, nb_stripes, nb_stripes_acc, input, secret)
+    }
+}
+
 //Internal function shared between Xxh3 and Xxh3Default
 fn xxh3_stateful_update(
     input: &[u8],
@@ -881,7 +1376,7 @@ fn xxh3_stateful_update(
     acc: &mut Acc,
     buffer: &mut Aligned64<[mem::MaybeUninit<u8>; INTERNAL_BUFFER_SIZE]>, buffered_size: &mut u16,
     nb_stripes_acc: &mut usize,
-    secret: &Aligned64<[u8; DEFAULT_SECRET_SIZE]>
+    secret: &[u8]
 ) {
     const INTERNAL_BUFFER_STRIPES: usize = INTERNAL_BUFFER_SIZE / STRIPE_LEN;
 
@@ -906,7 +1401,7 @@ fn xxh3_stateful_update(
             input_len -= fill_len;
         }
 
-        *nb_stripes_acc = xxh3_stateful_consume_stripes(acc, INTERNAL_BUFFER_STRIPES, *nb_stripes_acc, buffer.0.as_ptr() as *const u8, &secret.0);
+        *nb_stripes_acc = xxh3_stateful_consume_stripes(acc, INTERNAL_BUFFER_STRIPES, *nb_stripes_acc, buffer.0.as_ptr() as *const u8, secret);
 
         *buffered_size = 0;
     }
@@ -914,7 +1409,7 @@ fn xxh3_stateful_update(
     debug_assert_ne!(input_len, 0);
     if input_len > INTERNAL_BUFFER_SIZE {
         loop {
-            *nb_stripes_acc = xxh3_stateful_consume_stripes(acc, INTERNAL_BUFFER_STRIPES, *nb_stripes_acc, input_ptr, &secret.0);
+            *nb_stripes_acc = xxh3_stateful_consume_stripes(acc, INTERNAL_BUFFER_STRIPES, *nb_stripes_acc, input_ptr, secret);
             input_ptr = unsafe {
                 input_ptr.add(INTERNAL_BUFFER_SIZE)
             };
@@ -940,14 +1435,14 @@ fn xxh3_stateful_update(
 
 #[inline(always)]
 //Internal function shared between Xxh3 and Xxh3Default
-fn xxh3_stateful_digest_internal(acc: &mut Acc, nb_stripes_acc: usize, buffer: &[u8], old_buffer: &[mem::MaybeUninit<u8>], secret: &Aligned64<[u8; DEFAULT_SECRET_SIZE]>) {
+fn xxh3_stateful_digest_internal_kernel<K: Kernel>(acc: &mut Acc, nb_stripes_acc: usize, buffer: &[u8], old_buffer: &[mem::MaybeUninit<u8>], secret: &[u8]) {
     if buffer.len() >= STRIPE_LEN {
         let nb_stripes = (buffer.len() - 1) / STRIPE_LEN;
-        xxh3_stateful_consume_stripes(acc, nb_stripes, nb_stripes_acc, buffer.as_ptr(), &secret.0);
+        xxh3_stateful_consume_stripes_kernel::<K>(acc, nb_stripes, nb_stripes_acc, buffer.as_ptr(), secret);
 
-        accumulate_512(acc,
+        K::accumulate_512(acc,
             get_aligned_chunk_ref(buffer, buffer.len() - STRIPE_LEN),
-            get_aligned_chunk_ref(&secret.0, DEFAULT_SECRET_SIZE - STRIPE_LEN - SECRET_LASTACC_START)
+            get_aligned_chunk_ref(secret, secret.len() - STRIPE_LEN - SECRET_LASTACC_START)
         );
     } else {
         let mut last_stripe = mem::MaybeUninit::<[u8; STRIPE_LEN]>::uninit();
@@ -960,7 +1455,19 @@ fn xxh3_stateful_digest_internal(acc: &mut Acc, nb_stripes_acc: usize, buffer: &
             slice::from_raw_parts(last_stripe.as_ptr() as *const u8, buffer.len() + catchup_size)
         };
 
-        accumulate_512(acc, get_aligned_chunk_ref(&last_stripe, 0), get_aligned_chunk_ref(&secret.0, DEFAULT_SECRET_SIZE - STRIPE_LEN - SECRET_LASTACC_START));
+        K::accumulate_512(acc, get_aligned_chunk_ref(&last_stripe, 0), get_aligned_chunk_ref(secret, secret.len() - STRIPE_LEN - SECRET_LASTACC_START));
+    }
+}
+
+#[inline(always)]
+fn xxh3_stateful_digest_internal(acc: &mut Acc, nb_stripes_acc: usize, buffer: &[u8], old_buffer: &[mem::MaybeUninit<u8>], secret: &[u8]) {
+    #[cfg(not(feature = "runtime_dispatch"))]
+    {
+        xxh3_stateful_digest_internal_kernel::<NativeKernel>(acc, nb_stripes_acc, buffer, old_buffer, secret)
+    }
+    #[cfg(feature = "runtime_dispatch")]
+    unsafe {
+        (dispatch::kernels().digest_internal)(acc, nb_stripes_acc, buffer, old_buffer, secret)
     }
 }
 
@@ -1021,13 +1528,27 @@ impl Xxh3Default {
     #[inline(always)]
     ///Hashes provided chunk
     pub fn update(&mut self, input: &[u8]) {
-        xxh3_stateful_update(input, &mut self.total_len, &mut self.acc, &mut self.buffer, &mut self.buffered_size, &mut self.nb_stripes_acc, &Self::DEFAULT_SECRET);
+        xxh3_stateful_update(input, &mut self.total_len, &mut self.acc, &mut self.buffer, &mut self.buffered_size, &mut self.nb_stripes_acc, &Self::DEFAULT_SECRET.0);
+    }
+
+    #[inline(always)]
+    //Integer written into empty hasher is stored as is, skipping generic update path
+    fn update_int<const N: usize>(&mut self, bytes: [u8; N]) {
+        if self.total_len == 0 {
+            unsafe {
+                ptr::copy_nonoverlapping(bytes.as_ptr(), self.buffer.0.as_mut_ptr() as *mut u8, N);
+            }
+            self.buffered_size = N as u16;
+            self.total_len = N as u64;
+        } else {
+            self.update(&bytes);
+        }
     }
 
     #[inline(never)]
     fn digest_mid_sized(&self) -> u64 {
         let mut acc = self.acc.clone();
-        xxh3_stateful_digest_internal(&mut acc, self.nb_stripes_acc, self.buffered_input(), self.processed_buffer(), &Self::DEFAULT_SECRET);
+        xxh3_stateful_digest_internal(&mut acc, self.nb_stripes_acc, self.buffered_input(), self.processed_buffer(), &Self::DEFAULT_SECRET.0);
 
         merge_accs(&mut acc, get_aligned_chunk_ref(&Self::DEFAULT_SECRET.0, SECRET_MERGEACCS_START),
                     self.total_len.wrapping_mul(xxh64::PRIME_1))
@@ -1036,7 +1557,7 @@ impl Xxh3Default {
     #[inline(never)]
     fn digest_mid_sized_128(&self) -> u128 {
         let mut acc = self.acc.clone();
-        xxh3_stateful_digest_internal(&mut acc, self.nb_stripes_acc, self.buffered_input(), self.processed_buffer(), &Self::DEFAULT_SECRET);
+        xxh3_stateful_digest_internal(&mut acc, self.nb_stripes_acc, self.buffered_input(), self.processed_buffer(), &Self::DEFAULT_SECRET.0);
 
         let low = merge_accs(&mut acc, get_aligned_chunk_ref(&Self::DEFAULT_SECRET.0, SECRET_MERGEACCS_START),
                                 self.total_len.wrapping_mul(xxh64::PRIME_1));
@@ -1071,24 +1592,252 @@ impl Xxh3Default {
     }
 }
 
-impl Default for Xxh3Default {
-    #[inline(always)]
-    fn default() -> Self {
-        Self::new()
-    }
-}
+//Tail of internal buffer is used to finalize hash when less than a stripe is buffered.
+//It is guaranteed to be initialized only once stripes were consumed.
+fn xxh3_state_last_stripe(buffer: &Aligned64<[mem::MaybeUninit<u8>; INTERNAL_BUFFER_SIZE]>, total_len: u64) -> &[u8] {
+    if total_len > INTERNAL_BUFFER_SIZE as u64 {
+        unsafe {
+            slice::from_raw_parts((buffer.0.as_ptr() as *const u8).add(INTERNAL_BUFFER_SIZE - STRIPE_LEN), STRIPE_LEN)
+        }
+    } else {
+        &[]
+    }
+}
+
+//Validates and restores internal buffer, returning its size.
+fn xxh3_state_restore_buffer(
+    buffer: &mut Aligned64<[mem::MaybeUninit<u8>; INTERNAL_BUFFER_SIZE]>,
+    input: &[u8],
+    last_stripe: &[u8],
+    total_len: u64,
+    nb_stripes_acc: u64,
+    secret: &[u8]
+) -> Result<u16, StateError> {
+    if input.len() > INTERNAL_BUFFER_SIZE || input.len() as u64 > total_len {
+        return Err(StateError::InvalidState);
+    } else if nb_stripes_acc >= ((secret.len() - STRIPE_LEN) / SECRET_CONSUME_RATE) as u64 {
+        return Err(StateError::InvalidState);
+    } else if total_len > INTERNAL_BUFFER_SIZE as u64 {
+        //Input is consumed in whole buffers, keeping remainder buffered.
+        let consumed_len = total_len - input.len() as u64;
+        let stripes_per_block = ((secret.len() - STRIPE_LEN) / SECRET_CONSUME_RATE) as u64;
+        if last_stripe.len() != STRIPE_LEN || input.is_empty() || (consumed_len & (INTERNAL_BUFFER_SIZE as u64 - 1)) != 0 {
+            return Err(StateError::InvalidState);
+        } else if nb_stripes_acc != (consumed_len / STRIPE_LEN as u64) % stripes_per_block {
+            return Err(StateError::InvalidState);
+        }
+    } else if !last_stripe.is_empty() || nb_stripes_acc != 0 || input.len() as u64 != total_len {
+        //Short input is hashed from buffer only, hence it must be fully buffered.
+        return Err(StateError::InvalidState);
+    }
+
+    unsafe {
+        ptr::copy_nonoverlapping(last_stripe.as_ptr(), (buffer.0.as_mut_ptr() as *mut u8).add(INTERNAL_BUFFER_SIZE - last_stripe.len()), last_stripe.len());
+        ptr::copy_nonoverlapping(input.as_ptr(), buffer.0.as_mut_ptr() as *mut u8, input.len());
+    }
+    Ok(input.len() as u16)
+}
+
+impl Xxh3Default {
+    const STATE_TAG: &'static [u8; 3] = b"X3D";
+    ///Size of exported state.
+    pub const STATE_SIZE: usize = 346;
+
+    ///Exports state, allowing to resume hashing later via [Xxh3Default::from_bytes].
+    ///
+    ///Layout, following common header described in [state](../state/index.html) module:
+    ///
+    ///- `total_len` as `u64`;
+    ///- Number of stripes accumulated in current block as `u32`;
+    ///- Size of buffered input as `u16`;
+    ///- 8 accumulators as `u64`;
+    ///- 256 bytes buffer, containing buffered input at the start and, once input exceeded buffer, last consumed stripe at the end, with unused bytes set to zero;
+    ///- `xxh3_64` checksum as `u64`.
+    pub fn to_bytes(&self) -> [u8; Self::STATE_SIZE] {
+        let mut result = [0; Self::STATE_SIZE];
+        let mut buffer = [0u8; INTERNAL_BUFFER_SIZE];
+        let last_stripe = xxh3_state_last_stripe(&self.buffer, self.total_len);
+        buffer[INTERNAL_BUFFER_SIZE - last_stripe.len()..].copy_from_slice(last_stripe);
+        buffer[..self.buffered_size as usize].copy_from_slice(self.buffered_input());
+
+        let mut writer = StateWriter::new(&mut result, Self::STATE_TAG);
+        writer.write_u64(self.total_len);
+        writer.write_u32(self.nb_stripes_acc as u32);
+        writer.write_u16(self.buffered_size);
+        for acc in self.acc.0.iter() {
+            writer.write_u64(*acc);
+        }
+        writer.write_bytes(&buffer);
+        let checksum = xxh3_64(writer.written());
+        writer.write_u64(checksum);
+
+        result
+    }
+
+    ///Restores state exported via [Xxh3Default::to_bytes].
+    pub fn from_bytes(bytes: &[u8; Self::STATE_SIZE]) -> Result<Self, StateError> {
+        let mut reader = StateReader::new(bytes, Self::STATE_TAG, mem::size_of::<u64>(), xxh3_64)?;
+        let total_len = reader.read_u64();
+        let nb_stripes_acc = reader.read_u32();
+        let buffered_size = reader.read_u16() as usize;
+        let mut acc = INITIAL_ACC;
+        for acc in acc.0.iter_mut() {
+            *acc = reader.read_u64();
+        }
+        let buffer = reader.read_bytes(INTERNAL_BUFFER_SIZE);
+
+        if buffered_size > INTERNAL_BUFFER_SIZE {
+            return Err(StateError::InvalidState);
+        }
+        let last_stripe = match total_len > INTERNAL_BUFFER_SIZE as u64 {
+            true => &buffer[INTERNAL_BUFFER_SIZE - STRIPE_LEN..],
+            false => &buffer[..0],
+        };
+        let unused = buffer.get(buffered_size..INTERNAL_BUFFER_SIZE - last_stripe.len()).unwrap_or(&[]);
+        if unused.iter().any(|byte| *byte != 0) {
+            return Err(StateError::InvalidState);
+        }
+        let input = &buffer[..buffered_size];
+
+        let mut result = Self::new();
+        result.buffered_size = xxh3_state_restore_buffer(&mut result.buffer, input, last_stripe, total_len, nb_stripes_acc as u64, &DEFAULT_SECRET)?;
+        result.acc = acc;
+        result.nb_stripes_acc = nb_stripes_acc as usize;
+        result.total_len = total_len;
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Xxh3Default")]
+struct Xxh3DefaultState {
+    version: u8,
+    acc: [u64; ACC_NB],
+    nb_stripes_acc: u64,
+    total_len: u64,
+    buffer: crate::serde_utils::ByteBuf<INTERNAL_BUFFER_SIZE>,
+    last_stripe: crate::serde_utils::ByteBuf<STRIPE_LEN>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Xxh3Default {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Xxh3DefaultState {
+            version: crate::state::STATE_VERSION,
+            acc: self.acc.0,
+            nb_stripes_acc: self.nb_stripes_acc as u64,
+            total_len: self.total_len,
+            buffer: crate::serde_utils::ByteBuf::new(self.buffered_input()),
+            last_stripe: crate::serde_utils::ByteBuf::new(xxh3_state_last_stripe(&self.buffer, self.total_len)),
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Xxh3Default {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let state = Xxh3DefaultState::deserialize(deserializer)?;
+        if state.version != crate::state::STATE_VERSION {
+            return Err(D::Error::custom(StateError::UnsupportedVersion));
+        }
+
+        let mut result = Self::new();
+        result.buffered_size = xxh3_state_restore_buffer(&mut result.buffer, state.buffer.as_slice(), state.last_stripe.as_slice(), state.total_len, state.nb_stripes_acc, &DEFAULT_SECRET).map_err(D::Error::custom)?;
+        result.acc = Acc(state.acc);
+        result.nb_stripes_acc = state.nb_stripes_acc as usize;
+        result.total_len = state.total_len;
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::OutputSizeUser for Xxh3Default {
+    type OutputSize = digest::consts::U8;
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for Xxh3Default {
+    #[inline(always)]
+    fn update(&mut self, input: &[u8]) {
+        Xxh3Default::update(self, input)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutput for Xxh3Default {
+    #[inline]
+    ///Writes hash in canonical big-endian form.
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.digest().to_be_bytes())
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Reset for Xxh3Default {
+    #[inline(always)]
+    fn reset(&mut self) {
+        Xxh3Default::reset(self)
+    }
+}
+
+impl Default for Xxh3Default {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 
+//Integers are written as native-endian bytes, same as default `Hasher` methods
 impl hash::Hasher for Xxh3Default {
     #[inline(always)]
     fn finish(&self) -> u64 {
-        self.digest()
+        //Single integer is hashed directly, skipping dispatch on input length
+        match self.total_len {
+            4 => hash_u32(read_32le_unaligned(self.buffered_input(), 0)),
+            8 => hash_u64(read_64le_unaligned(self.buffered_input(), 0)),
+            16 => hash_u128(read_128le_unaligned(self.buffered_input(), 0)),
+            _ => self.digest(),
+        }
     }
 
     #[inline(always)]
     fn write(&mut self, input: &[u8]) {
         self.update(input)
     }
+
+    #[inline(always)]
+    fn write_u8(&mut self, value: u8) {
+        self.update_int(value.to_ne_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u16(&mut self, value: u16) {
+        self.update_int(value.to_ne_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, value: u32) {
+        self.update_int(value.to_ne_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, value: u64) {
+        self.update_int(value.to_ne_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u128(&mut self, value: u128) {
+        self.update_int(value.to_ne_bytes())
+    }
+
+    #[inline(always)]
+    fn write_usize(&mut self, value: usize) {
+        self.update_int(value.to_ne_bytes())
+    }
 }
 
 #[cfg(feature = "std")]
@@ -1105,6 +1854,62 @@ impl std::io::Write for Xxh3Default {
     }
 }
 
+#[cfg(feature = "std")]
+//Multiple of internal buffer, so that every read fills whole internal buffers
+const READ_BUFFER_SIZE: usize = INTERNAL_BUFFER_SIZE * 64;
+
+#[cfg(feature = "std")]
+///Returns 64bit hash for content of `reader`, read until its end.
+pub fn hash_reader<R: std::io::Read>(reader: R) -> std::io::Result<u64> {
+    let mut hasher = Xxh3Default::new();
+    let mut buffer = [0u8; READ_BUFFER_SIZE];
+    crate::utils::update_from_reader(reader, &mut buffer, |input| hasher.update(input))?;
+    Ok(hasher.digest())
+}
+
+#[cfg(feature = "std")]
+///Returns 128bit hash for content of `reader`, read until its end.
+pub fn hash_reader128<R: std::io::Read>(reader: R) -> std::io::Result<u128> {
+    let mut hasher = Xxh3Default::new();
+    let mut buffer = [0u8; READ_BUFFER_SIZE];
+    crate::utils::update_from_reader(reader, &mut buffer, |input| hasher.update(input))?;
+    Ok(hasher.digest128())
+}
+
+#[cfg(feature = "std")]
+#[inline]
+///Returns 64bit hash for content of file at `path`.
+pub fn hash_file<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<u64> {
+    hash_reader(std::fs::File::open(path)?)
+}
+
+#[cfg(feature = "std")]
+#[inline]
+///Returns 128bit hash for content of file at `path`.
+pub fn hash_file128<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<u128> {
+    hash_reader128(std::fs::File::open(path)?)
+}
+
+#[cfg(feature = "mmap")]
+///Returns 64bit hash for content of file at `path`, hashing memory-mapped file via [xxh3_64].
+///
+///Falls back to [hash_reader] for small files and files that cannot be mapped, such as pipes.
+///
+///Result is unspecified if file is modified while being hashed.
+pub fn hash_file_mmap<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<u64> {
+    crate::utils::hash_file_mmap(path.as_ref(), xxh3_64, hash_reader)
+}
+
+#[cfg(feature = "mmap")]
+///Returns 128bit hash for content of file at `path`, hashing memory-mapped file via [xxh3_128].
+///
+///Falls back to [hash_reader128] for small files and files that cannot be mapped, such as pipes.
+///
+///Result is unspecified if file is modified while being hashed.
+pub fn hash_file128_mmap<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<u128> {
+    crate::utils::hash_file_mmap(path.as_ref(), xxh3_128, hash_reader128)
+}
+
 #[derive(Clone)]
 ///XXH3 Streaming algorithm
 ///
@@ -1122,6 +1927,7 @@ pub struct Xxh3 {
     nb_stripes_acc: usize,
     total_len: u64,
     seed: u64,
+    use_seed: bool,
 }
 
 impl Xxh3 {
@@ -1142,6 +1948,7 @@ impl Xxh3 {
             nb_stripes_acc: 0,
             total_len: 0,
             seed,
+            use_seed: seed > 0,
         }
     }
 
@@ -1157,6 +1964,582 @@ impl Xxh3 {
         Self::with_custom_ops(seed, custom_default_secret(seed))
     }
 
+    #[inline(always)]
+    ///Creates new hasher with custom secret and seed.
+    ///
+    ///Seed is used for inputs up to `MID_SIZE_MAX`, while secret is used for larger inputs,
+    ///avoiding cost of deriving secret from seed.
+    ///
+    ///Produces the same output as [xxh3_64_with_secret_and_seed] and [xxh3_128_with_secret_and_seed].
+    pub const fn with_secret_and_seed(secret: [u8; DEFAULT_SECRET_SIZE], seed: u64) -> Self {
+        let mut result = Self::with_custom_ops(seed, secret);
+        result.use_seed = true;
+        result
+    }
+
+    #[inline(always)]
+    ///Resets state
+    pub fn reset(&mut self) {
+        self.acc = INITIAL_ACC;
+        self.total_len = 0;
+        self.buffered_size = 0;
+        self.nb_stripes_acc = 0;
+    }
+
+    #[inline(always)]
+    fn buffered_input(&self) -> &[u8] {
+        let ptr = self.buffer.0.as_ptr();
+        unsafe {
+            slice::from_raw_parts(ptr as *const u8, self.buffered_size as usize)
+        }
+    }
+
+    #[inline(always)]
+    fn processed_buffer(&self) -> &[mem::MaybeUninit<u8>] {
+        let ptr = self.buffer.0.as_ptr();
+        unsafe {
+            slice::from_raw_parts(ptr.add(self.buffered_size as usize), self.buffer.0.len() - self.buffered_size as usize)
+        }
+    }
+
+    #[inline]
+    ///Hashes provided chunk
+    pub fn update(&mut self, input: &[u8]) {
+        xxh3_stateful_update(input, &mut self.total_len, &mut self.acc, &mut self.buffer, &mut self.buffered_size, &mut self.nb_stripes_acc, &self.custom_secret.0);
+    }
+
+    #[inline(always)]
+    //Integer written into empty hasher is stored as is, skipping generic update path
+    fn update_int<const N: usize>(&mut self, bytes: [u8; N]) {
+        if self.total_len == 0 {
+            unsafe {
+                ptr::copy_nonoverlapping(bytes.as_ptr(), self.buffer.0.as_mut_ptr() as *mut u8, N);
+            }
+            self.buffered_size = N as u16;
+            self.total_len = N as u64;
+        } else {
+            self.update(&bytes);
+        }
+    }
+
+    #[inline(never)]
+    fn digest_mid_sized(&self) -> u64 {
+        let mut acc = self.acc.clone();
+        xxh3_stateful_digest_internal(&mut acc, self.nb_stripes_acc, self.buffered_input(), self.processed_buffer(), &self.custom_secret.0);
+
+        merge_accs(&mut acc, get_aligned_chunk_ref(&self.custom_secret.0, SECRET_MERGEACCS_START),
+                    self.total_len.wrapping_mul(xxh64::PRIME_1))
+    }
+
+    #[inline(never)]
+    fn digest_mid_sized_128(&self) -> u128 {
+        let mut acc = self.acc.clone();
+        xxh3_stateful_digest_internal(&mut acc, self.nb_stripes_acc, self.buffered_input(), self.processed_buffer(), &self.custom_secret.0);
+
+        let low = merge_accs(&mut acc, get_aligned_chunk_ref(&self.custom_secret.0, SECRET_MERGEACCS_START), self.total_len.wrapping_mul(xxh64::PRIME_1));
+        let high = merge_accs(&mut acc, get_aligned_chunk_ref(&self.custom_secret.0, self.custom_secret.0.len() - mem::size_of_val(&self.acc) - SECRET_MERGEACCS_START), !self.total_len.wrapping_mul(xxh64::PRIME_2));
+        ((high as u128) << 64) | (low as u128)
+    }
+
+    #[inline]
+    ///Computes hash.
+    pub fn digest(&self) -> u64 {
+        //Separating digest mid sized allows us to inline this function, which benefits
+        //code generation when hashing fixed size types and/or if the seed is known.
+        if self.total_len > MID_SIZE_MAX as u64 {
+            self.digest_mid_sized()
+        } else if self.use_seed {
+            //Technically we should not need to use it.
+            //But in all actuality original xxh3 implementation uses default secret for input with size less or equal to MID_SIZE_MAX
+            xxh3_64_internal(self.buffered_input(), self.seed, &DEFAULT_SECRET, xxh3_64_long_with_seed)
+        } else {
+            xxh3_64_internal(self.buffered_input(), self.seed, &self.custom_secret.0, xxh3_64_long_with_secret)
+        }
+    }
+
+    #[inline]
+    ///Computes hash as 128bit integer.
+    pub fn digest128(&self) -> u128 {
+        //Separating digest mid sized allows us to inline this function, which benefits
+        //code generation when hashing fixed size types and/or if the seed is known.
+        if self.total_len > MID_SIZE_MAX as u64 {
+            self.digest_mid_sized_128()
+        } else if self.use_seed {
+            //Technically we should not need to use it.
+            //But in all actuality original xxh3 implementation uses default secret for input with size less or equal to MID_SIZE_MAX
+            xxh3_128_internal(self.buffered_input(), self.seed, &DEFAULT_SECRET, xxh3_128_long_with_seed)
+        } else {
+            xxh3_128_internal(self.buffered_input(), self.seed, &self.custom_secret.0, xxh3_128_long_with_secret)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Xxh3")]
+struct Xxh3State {
+    version: u8,
+    acc: [u64; ACC_NB],
+    nb_stripes_acc: u64,
+    total_len: u64,
+    buffer: crate::serde_utils::ByteBuf<INTERNAL_BUFFER_SIZE>,
+    last_stripe: crate::serde_utils::ByteBuf<STRIPE_LEN>,
+    secret: crate::serde_utils::ByteBuf<DEFAULT_SECRET_SIZE>,
+    seed: u64,
+    use_seed: bool,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Xxh3 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Xxh3State {
+            version: crate::state::STATE_VERSION,
+            acc: self.acc.0,
+            nb_stripes_acc: self.nb_stripes_acc as u64,
+            total_len: self.total_len,
+            buffer: crate::serde_utils::ByteBuf::new(self.buffered_input()),
+            last_stripe: crate::serde_utils::ByteBuf::new(xxh3_state_last_stripe(&self.buffer, self.total_len)),
+            secret: crate::serde_utils::ByteBuf::new(&self.custom_secret.0),
+            seed: self.seed,
+            use_seed: self.use_seed,
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Xxh3 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let state = Xxh3State::deserialize(deserializer)?;
+        if state.version != crate::state::STATE_VERSION {
+            return Err(D::Error::custom(StateError::UnsupportedVersion));
+        } else if state.secret.as_slice().len() != DEFAULT_SECRET_SIZE {
+            return Err(D::Error::custom(StateError::InvalidState));
+        }
+
+        let mut secret = [0; DEFAULT_SECRET_SIZE];
+        secret.copy_from_slice(state.secret.as_slice());
+        let mut result = Self::with_custom_ops(state.seed, secret);
+        result.use_seed = state.use_seed;
+        result.buffered_size = xxh3_state_restore_buffer(&mut result.buffer, state.buffer.as_slice(), state.last_stripe.as_slice(), state.total_len, state.nb_stripes_acc, &secret).map_err(D::Error::custom)?;
+        result.acc = Acc(state.acc);
+        result.nb_stripes_acc = state.nb_stripes_acc as usize;
+        result.total_len = state.total_len;
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::OutputSizeUser for Xxh3 {
+    type OutputSize = digest::consts::U8;
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for Xxh3 {
+    #[inline(always)]
+    fn update(&mut self, input: &[u8]) {
+        Xxh3::update(self, input)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutput for Xxh3 {
+    #[inline]
+    ///Writes hash in canonical big-endian form.
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.digest().to_be_bytes())
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Reset for Xxh3 {
+    #[inline(always)]
+    fn reset(&mut self) {
+        Xxh3::reset(self)
+    }
+}
+
+#[cfg(feature = "digest")]
+#[derive(Clone, Default)]
+///Wrapper over [Xxh3](struct.Xxh3.html) producing 128bit output via `digest` traits
+pub struct Xxh128(pub Xxh3);
+
+#[cfg(feature = "digest")]
+impl digest::OutputSizeUser for Xxh128 {
+    type OutputSize = digest::consts::U16;
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for Xxh128 {
+    #[inline(always)]
+    fn update(&mut self, input: &[u8]) {
+        self.0.update(input)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutput for Xxh128 {
+    #[inline]
+    ///Writes hash in canonical big-endian form.
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.0.digest128().to_be_bytes())
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Reset for Xxh128 {
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.0.reset()
+    }
+}
+
+impl Default for Xxh3 {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//Integers are written as native-endian bytes, same as default `Hasher` methods
+impl core::hash::Hasher for Xxh3 {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        //Single integer is hashed directly, skipping dispatch on input length.
+        //Short input is hashed with default secret only when seed is used
+        if !self.use_seed {
+            return self.digest();
+        }
+
+        match self.total_len {
+            4 => hash_u32_with_seed(read_32le_unaligned(self.buffered_input(), 0), self.seed),
+            8 => hash_u64_with_seed(read_64le_unaligned(self.buffered_input(), 0), self.seed),
+            16 => hash_u128_with_seed(read_128le_unaligned(self.buffered_input(), 0), self.seed),
+            _ => self.digest(),
+        }
+    }
+
+    #[inline(always)]
+    fn write(&mut self, input: &[u8]) {
+        self.update(input)
+    }
+
+    #[inline(always)]
+    fn write_u8(&mut self, value: u8) {
+        self.update_int(value.to_ne_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u16(&mut self, value: u16) {
+        self.update_int(value.to_ne_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u32(&mut self, value: u32) {
+        self.update_int(value.to_ne_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, value: u64) {
+        self.update_int(value.to_ne_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u128(&mut self, value: u128) {
+        self.update_int(value.to_ne_bytes())
+    }
+
+    #[inline(always)]
+    fn write_usize(&mut self, value: usize) {
+        self.update_int(value.to_ne_bytes())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for Xxh3 {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+///XXH3 Streaming algorithm with custom secret of arbitrary length
+///
+///Unlike [Xxh3](struct.Xxh3.html), it accepts any secret with length of at least `SECRET_SIZE_MIN`,
+///which can be borrowed (e.g. `&[u8]`) to avoid copying.
+///
+///Produces the same output as [xxh3_64_with_secret] and [xxh3_128_with_secret].
+pub struct Xxh3WithSecret<S> {
+    acc: Acc,
+    buffer: Aligned64<[mem::MaybeUninit<u8>; INTERNAL_BUFFER_SIZE]>,
+    buffered_size: u16,
+    nb_stripes_acc: usize,
+    total_len: u64,
+    secret: SecretInput<S>,
+}
+
+impl<S> Xxh3WithSecret<S> {
+    #[inline(always)]
+    ///Creates new hasher with provided secret.
+    pub const fn new(secret: SecretInput<S>) -> Self {
+        Self {
+            acc: INITIAL_ACC,
+            buffer: Aligned64([mem::MaybeUninit::uninit(); INTERNAL_BUFFER_SIZE]),
+            buffered_size: 0,
+            nb_stripes_acc: 0,
+            total_len: 0,
+            secret,
+        }
+    }
+
+    #[inline(always)]
+    ///Resets state
+    pub fn reset(&mut self) {
+        self.acc = INITIAL_ACC;
+        self.total_len = 0;
+        self.buffered_size = 0;
+        self.nb_stripes_acc = 0;
+    }
+
+    #[inline(always)]
+    ///Returns secret used by hasher.
+    pub fn secret(&self) -> &SecretInput<S> {
+        &self.secret
+    }
+
+    #[inline(always)]
+    fn buffered_input(&self) -> &[u8] {
+        let ptr = self.buffer.0.as_ptr();
+        unsafe {
+            slice::from_raw_parts(ptr as *const u8, self.buffered_size as usize)
+        }
+    }
+
+    #[inline(always)]
+    fn processed_buffer(&self) -> &[mem::MaybeUninit<u8>] {
+        let ptr = self.buffer.0.as_ptr();
+        unsafe {
+            slice::from_raw_parts(ptr.add(self.buffered_size as usize), self.buffer.0.len() - self.buffered_size as usize)
+        }
+    }
+}
+
+impl<S: AsRef<[u8]>> Xxh3WithSecret<S> {
+    #[inline]
+    ///Hashes provided chunk
+    pub fn update(&mut self, input: &[u8]) {
+        xxh3_stateful_update(input, &mut self.total_len, &mut self.acc, &mut self.buffer, &mut self.buffered_size, &mut self.nb_stripes_acc, self.secret.0.as_ref());
+    }
+
+    #[inline(never)]
+    fn digest_mid_sized(&self) -> u64 {
+        let secret = self.secret.0.as_ref();
+        let mut acc = self.acc.clone();
+        xxh3_stateful_digest_internal(&mut acc, self.nb_stripes_acc, self.buffered_input(), self.processed_buffer(), secret);
+
+        merge_accs(&mut acc, get_aligned_chunk_ref(secret, SECRET_MERGEACCS_START),
+                    self.total_len.wrapping_mul(xxh64::PRIME_1))
+    }
+
+    #[inline(never)]
+    fn digest_mid_sized_128(&self) -> u128 {
+        let secret = self.secret.0.as_ref();
+        let mut acc = self.acc.clone();
+        xxh3_stateful_digest_internal(&mut acc, self.nb_stripes_acc, self.buffered_input(), self.processed_buffer(), secret);
+
+        let low = merge_accs(&mut acc, get_aligned_chunk_ref(secret, SECRET_MERGEACCS_START), self.total_len.wrapping_mul(xxh64::PRIME_1));
+        let high = merge_accs(&mut acc, get_aligned_chunk_ref(secret, secret.len() - mem::size_of_val(&self.acc) - SECRET_MERGEACCS_START), !self.total_len.wrapping_mul(xxh64::PRIME_2));
+        ((high as u128) << 64) | (low as u128)
+    }
+
+    #[inline]
+    ///Computes hash.
+    pub fn digest(&self) -> u64 {
+        if self.total_len > MID_SIZE_MAX as u64 {
+            self.digest_mid_sized()
+        } else {
+            xxh3_64_internal(self.buffered_input(), 0, self.secret.0.as_ref(), xxh3_64_long_with_secret)
+        }
+    }
+
+    #[inline]
+    ///Computes hash as 128bit integer.
+    pub fn digest128(&self) -> u128 {
+        if self.total_len > MID_SIZE_MAX as u64 {
+            self.digest_mid_sized_128()
+        } else {
+            xxh3_128_internal(self.buffered_input(), 0, self.secret.0.as_ref(), xxh3_128_long_with_secret)
+        }
+    }
+}
+
+impl<S: AsRef<[u8]>> hash::Hasher for Xxh3WithSecret<S> {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.digest()
+    }
+
+    #[inline(always)]
+    fn write(&mut self, input: &[u8]) {
+        self.update(input)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: AsRef<[u8]>> std::io::Write for Xxh3WithSecret<S> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+///Hash builder for `Xxh3`
+pub struct Xxh3Builder {
+    seed: Option<u64>,
+    secret: Option<[u8; DEFAULT_SECRET_SIZE]>,
+}
+
+impl Xxh3Builder {
+    #[inline(always)]
+    ///Creates new instance with default params.
+    pub const fn new() -> Self {
+        Self {
+            seed: None,
+            secret: None,
+        }
+    }
+
+    #[inline(always)]
+    ///Sets `seed` for `xxh3` algorithm
+    ///
+    ///This method overrides default secret with custom based on seed.
+    ///
+    ///To counter it, override secret using [Xxh3Builder::with_secret]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    #[inline(always)]
+    ///Sets custom `secret` for `xxh3` algorithm
+    pub const fn with_secret(mut self, secret: [u8; DEFAULT_SECRET_SIZE]) -> Self {
+        self.secret = Some(secret);
+        self
+    }
+
+    #[inline(always)]
+    ///Creates `Xxh3` instance
+    pub const fn build(self) -> Xxh3 {
+        let (seed, secret) = match (self.seed, self.secret) {
+            (Some(seed), Some(secret)) => (seed, secret),
+            (Some(seed), None) => (seed, const_custom_default_secret(seed)),
+            (None, Some(secret)) => (0, secret),
+            (None, None) => (0, DEFAULT_SECRET),
+        };
+        Xxh3::with_custom_ops(seed, secret)
+    }
+}
+
+impl core::hash::BuildHasher for Xxh3Builder {
+    type Hasher = Xxh3;
+
+    #[inline(always)]
+    fn build_hasher(&self) -> Self::Hasher {
+        self.build()
+    }
+}
+
+impl Default for Xxh3Builder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Copy)]
+///Hash builder for `Xxh3Default`
+pub struct Xxh3DefaultBuilder;
+
+impl Xxh3DefaultBuilder {
+    #[inline(always)]
+    ///Creates new instance with default params.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    #[inline(always)]
+    ///Creates `Xxh3` instance
+    pub const fn build(self) -> Xxh3Default {
+        Xxh3Default::new()
+    }
+}
+
+impl core::hash::BuildHasher for Xxh3DefaultBuilder {
+    type Hasher = Xxh3Default;
+
+    #[inline(always)]
+    fn build_hasher(&self) -> Self::Hasher {
+        self.build()
+    }
+}
+
+impl Default for Xxh3DefaultBuilder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone)]
+///Lightweight `Xxh3` hasher for small keys, such as `HashMap` keys.
+///
+///Input up to `MID_SIZE_MAX` bytes is accumulated inline and hashed at once with one-shot functions.
+///Longer input is streamed through the same buffer, deriving secret from seed only at that point.
+///Output is the same as [xxh3_64_with_seed] over all written bytes.
+pub struct Xxh3Fast {
+    acc: Acc,
+    buffer: Aligned64<[mem::MaybeUninit<u8>; INTERNAL_BUFFER_SIZE]>,
+    buffered_size: u16,
+    nb_stripes_acc: usize,
+    total_len: u64,
+    seed: u64,
+}
+
+impl Xxh3Fast {
+    #[inline(always)]
+    ///Creates new hasher with default settings
+    pub const fn new() -> Self {
+        Self::with_seed(0)
+    }
+
+    #[inline(always)]
+    ///Creates new hasher with specified seed.
+    pub const fn with_seed(seed: u64) -> Self {
+        Self {
+            acc: INITIAL_ACC,
+            buffer: Aligned64([mem::MaybeUninit::uninit(); INTERNAL_BUFFER_SIZE]),
+            buffered_size: 0,
+            nb_stripes_acc: 0,
+            total_len: 0,
+            seed,
+        }
+    }
+
     #[inline(always)]
     ///Resets state
     pub fn reset(&mut self) {
@@ -1166,6 +2549,15 @@ impl Xxh3 {
         self.nb_stripes_acc = 0;
     }
 
+    #[inline(always)]
+    fn secret(&self) -> [u8; DEFAULT_SECRET_SIZE] {
+        if self.seed == 0 {
+            DEFAULT_SECRET
+        } else {
+            custom_default_secret(self.seed)
+        }
+    }
+
     #[inline(always)]
     fn buffered_input(&self) -> &[u8] {
         let ptr = self.buffer.0.as_ptr();
@@ -1182,72 +2574,77 @@ impl Xxh3 {
         }
     }
 
+    #[inline(never)]
+    fn update_long(&mut self, input: &[u8]) {
+        let secret = self.secret();
+        xxh3_stateful_update(input, &mut self.total_len, &mut self.acc, &mut self.buffer, &mut self.buffered_size, &mut self.nb_stripes_acc, &secret);
+    }
+
     #[inline]
     ///Hashes provided chunk
     pub fn update(&mut self, input: &[u8]) {
-        xxh3_stateful_update(input, &mut self.total_len, &mut self.acc, &mut self.buffer, &mut self.buffered_size, &mut self.nb_stripes_acc, &self.custom_secret);
+        let offset = self.buffered_size as usize;
+        if input.len() <= INTERNAL_BUFFER_SIZE - offset {
+            unsafe {
+                ptr::copy_nonoverlapping(input.as_ptr(), (self.buffer.0.as_mut_ptr() as *mut u8).add(offset), input.len());
+            }
+            self.buffered_size = (offset + input.len()) as u16;
+            self.total_len = self.total_len.wrapping_add(input.len() as u64);
+        } else {
+            self.update_long(input);
+        }
     }
 
     #[inline(never)]
     fn digest_mid_sized(&self) -> u64 {
+        let secret = self.secret();
         let mut acc = self.acc.clone();
-        xxh3_stateful_digest_internal(&mut acc, self.nb_stripes_acc, self.buffered_input(), self.processed_buffer(), &self.custom_secret);
+        xxh3_stateful_digest_internal(&mut acc, self.nb_stripes_acc, self.buffered_input(), self.processed_buffer(), &secret);
 
-        merge_accs(&mut acc, get_aligned_chunk_ref(&self.custom_secret.0, SECRET_MERGEACCS_START),
+        merge_accs(&mut acc, get_aligned_chunk_ref(&secret, SECRET_MERGEACCS_START),
                     self.total_len.wrapping_mul(xxh64::PRIME_1))
     }
 
     #[inline(never)]
     fn digest_mid_sized_128(&self) -> u128 {
+        let secret = self.secret();
         let mut acc = self.acc.clone();
-        xxh3_stateful_digest_internal(&mut acc, self.nb_stripes_acc, self.buffered_input(), self.processed_buffer(), &self.custom_secret);
+        xxh3_stateful_digest_internal(&mut acc, self.nb_stripes_acc, self.buffered_input(), self.processed_buffer(), &secret);
 
-        let low = merge_accs(&mut acc, get_aligned_chunk_ref(&self.custom_secret.0, SECRET_MERGEACCS_START), self.total_len.wrapping_mul(xxh64::PRIME_1));
-        let high = merge_accs(&mut acc, get_aligned_chunk_ref(&self.custom_secret.0, self.custom_secret.0.len() - mem::size_of_val(&self.acc) - SECRET_MERGEACCS_START), !self.total_len.wrapping_mul(xxh64::PRIME_2));
+        let low = merge_accs(&mut acc, get_aligned_chunk_ref(&secret, SECRET_MERGEACCS_START), self.total_len.wrapping_mul(xxh64::PRIME_1));
+        let high = merge_accs(&mut acc, get_aligned_chunk_ref(&secret, secret.len() - mem::size_of_val(&self.acc) - SECRET_MERGEACCS_START), !self.total_len.wrapping_mul(xxh64::PRIME_2));
         ((high as u128) << 64) | (low as u128)
     }
 
     #[inline]
     ///Computes hash.
     pub fn digest(&self) -> u64 {
-        //Separating digest mid sized allows us to inline this function, which benefits
-        //code generation when hashing fixed size types and/or if the seed is known.
         if self.total_len > MID_SIZE_MAX as u64 {
             self.digest_mid_sized()
-        } else if self.seed > 0 {
-            //Technically we should not need to use it.
-            //But in all actuality original xxh3 implementation uses default secret for input with size less or equal to MID_SIZE_MAX
-            xxh3_64_internal(self.buffered_input(), self.seed, &DEFAULT_SECRET, xxh3_64_long_with_seed)
         } else {
-            xxh3_64_internal(self.buffered_input(), self.seed, &self.custom_secret.0, xxh3_64_long_with_secret)
+            xxh3_64_internal(self.buffered_input(), self.seed, &DEFAULT_SECRET, xxh3_64_long_with_seed)
         }
     }
 
     #[inline]
     ///Computes hash as 128bit integer.
     pub fn digest128(&self) -> u128 {
-        //Separating digest mid sized allows us to inline this function, which benefits
-        //code generation when hashing fixed size types and/or if the seed is known.
         if self.total_len > MID_SIZE_MAX as u64 {
             self.digest_mid_sized_128()
-        } else if self.seed > 0 {
-            //Technically we should not need to use it.
-            //But in all actuality original xxh3 implementation uses default secret for input with size less or equal to MID_SIZE_MAX
-            xxh3_128_internal(self.buffered_input(), self.seed, &DEFAULT_SECRET, xxh3_128_long_with_seed)
         } else {
-            xxh3_128_internal(self.buffered_input(), self.seed, &self.custom_secret.0, xxh3_128_long_with_secret)
+            xxh3_128_internal(self.buffered_input(), self.seed, &DEFAULT_SECRET, xxh3_128_long_with_seed)
         }
     }
 }
 
-impl Default for Xxh3 {
+impl Default for Xxh3Fast {
     #[inline(always)]
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl core::hash::Hasher for Xxh3 {
+impl hash::Hasher for Xxh3Fast {
     #[inline(always)]
     fn finish(&self) -> u64 {
         self.digest()
@@ -1259,70 +2656,37 @@ impl core::hash::Hasher for Xxh3 {
     }
 }
 
-#[cfg(feature = "std")]
-impl std::io::Write for Xxh3 {
-    #[inline]
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.update(buf);
-        Ok(buf.len())
-    }
-
-    #[inline]
-    fn flush(&mut self) -> std::io::Result<()> {
-        Ok(())
-    }
-}
-
-#[derive(Clone, Copy)]
-///Hash builder for `Xxh3`
-pub struct Xxh3Builder {
-    seed: Option<u64>,
-    secret: Option<[u8; DEFAULT_SECRET_SIZE]>,
+#[derive(Clone, Copy, Default)]
+///Hash builder for `Xxh3Fast`
+pub struct Xxh3FastBuilder {
+    seed: u64,
 }
 
-impl Xxh3Builder {
+impl Xxh3FastBuilder {
     #[inline(always)]
     ///Creates new instance with default params.
     pub const fn new() -> Self {
         Self {
-            seed: None,
-            secret: None,
+            seed: 0,
         }
     }
 
     #[inline(always)]
     ///Sets `seed` for `xxh3` algorithm
-    ///
-    ///This method overrides default secret with custom based on seed.
-    ///
-    ///To counter it, override secret using [Xxh3Builder::with_secret]
     pub const fn with_seed(mut self, seed: u64) -> Self {
-        self.seed = Some(seed);
-        self
-    }
-
-    #[inline(always)]
-    ///Sets custom `secret` for `xxh3` algorithm
-    pub const fn with_secret(mut self, secret: [u8; DEFAULT_SECRET_SIZE]) -> Self {
-        self.secret = Some(secret);
+        self.seed = seed;
         self
     }
 
     #[inline(always)]
-    ///Creates `Xxh3` instance
-    pub const fn build(self) -> Xxh3 {
-        let (seed, secret) = match (self.seed, self.secret) {
-            (Some(seed), Some(secret)) => (seed, secret),
-            (Some(seed), None) => (seed, const_custom_default_secret(seed)),
-            (None, Some(secret)) => (0, secret),
-            (None, None) => (0, DEFAULT_SECRET),
-        };
-        Xxh3::with_custom_ops(seed, secret)
+    ///Creates `Xxh3Fast` instance
+    pub const fn build(self) -> Xxh3Fast {
+        Xxh3Fast::with_seed(self.seed)
     }
 }
 
-impl core::hash::BuildHasher for Xxh3Builder {
-    type Hasher = Xxh3;
+impl core::hash::BuildHasher for Xxh3FastBuilder {
+    type Hasher = Xxh3Fast;
 
     #[inline(always)]
     fn build_hasher(&self) -> Self::Hasher {
@@ -1330,33 +2694,72 @@ impl core::hash::BuildHasher for Xxh3Builder {
     }
 }
 
-impl Default for Xxh3Builder {
-    #[inline(always)]
-    fn default() -> Self {
-        Self::new()
+#[cfg(feature = "std")]
+///`HashMap` using [Xxh3Fast] hasher.
+pub type Xxh3HashMap<K, V> = std::collections::HashMap<K, V, Xxh3FastBuilder>;
+#[cfg(feature = "std")]
+///`HashSet` using [Xxh3Fast] hasher.
+pub type Xxh3HashSet<T> = std::collections::HashSet<T, Xxh3FastBuilder>;
+
+#[cfg(feature = "std")]
+//Random value obtained from OS once per process, via std's `RandomState` keys
+fn process_random_seed() -> u64 {
+    use core::sync::atomic::{AtomicU64, Ordering};
+    use std::hash::{BuildHasher, Hasher};
+
+    static SEED: AtomicU64 = AtomicU64::new(0);
+
+    match SEED.load(Ordering::Relaxed) {
+        0 => {
+            let seed = std::collections::hash_map::RandomState::new().build_hasher().finish() | 1;
+            //Racing threads may generate different seeds, but only the first one is ever used
+            match SEED.compare_exchange(0, seed, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => seed,
+                Err(seed) => seed,
+            }
+        },
+        seed => seed,
     }
 }
 
-#[derive(Clone, Copy)]
-///Hash builder for `Xxh3Default`
-pub struct Xxh3DefaultBuilder;
+#[cfg(feature = "std")]
+#[derive(Clone)]
+///Randomly seeded hash builder for `Xxh3`, resistant to HashDoS.
+///
+///Similarly to `std::collections::hash_map::RandomState`, random seed is obtained from OS once per process
+///and then perturbed for every new instance.
+///Custom secret is derived from seed once per instance, protecting long keys too.
+pub struct RandomXxh3State {
+    seed: u64,
+    secret: [u8; DEFAULT_SECRET_SIZE],
+}
 
-impl Xxh3DefaultBuilder {
-    #[inline(always)]
-    ///Creates new instance with default params.
-    pub const fn new() -> Self {
-        Self
+#[cfg(feature = "std")]
+impl RandomXxh3State {
+    ///Creates new instance with unique random seed.
+    pub fn new() -> Self {
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let seed = xxh64::avalanche(process_random_seed() ^ counter.wrapping_mul(xxh64::PRIME_1));
+        Self {
+            seed,
+            secret: custom_default_secret(seed),
+        }
     }
 
     #[inline(always)]
     ///Creates `Xxh3` instance
-    pub const fn build(self) -> Xxh3Default {
-        Xxh3Default::new()
+    pub fn build(&self) -> Xxh3 {
+        Xxh3::with_custom_ops(self.seed, self.secret)
     }
 }
 
-impl core::hash::BuildHasher for Xxh3DefaultBuilder {
-    type Hasher = Xxh3Default;
+#[cfg(feature = "std")]
+impl core::hash::BuildHasher for RandomXxh3State {
+    type Hasher = Xxh3;
 
     #[inline(always)]
     fn build_hasher(&self) -> Self::Hasher {
@@ -1364,22 +2767,31 @@ impl core::hash::BuildHasher for Xxh3DefaultBuilder {
     }
 }
 
-impl Default for Xxh3DefaultBuilder {
+#[cfg(feature = "std")]
+impl Default for RandomXxh3State {
     #[inline(always)]
     fn default() -> Self {
         Self::new()
     }
 }
 
+#[cfg(feature = "std")]
+impl core::fmt::Debug for RandomXxh3State {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        //Seed must not leak
+        fmt.write_str("RandomXxh3State { .. }")
+    }
+}
+
 //
 //128bit
 //
 
-#[inline]
-fn xxh3_128_long_impl(input: &[u8], secret: &[u8]) -> u128 {
+#[inline(always)]
+fn xxh3_128_long_kernel<K: Kernel>(input: &[u8], secret: &[u8]) -> u128 {
     let mut acc = INITIAL_ACC;
 
-    hash_long_internal_loop(&mut acc, input, secret);
+    hash_long_internal_loop::<K>(&mut acc, input, secret);
 
     debug_assert!(secret.len() >= mem::size_of::<Acc>() + SECRET_MERGEACCS_START);
     let lo = merge_accs(&mut acc, get_aligned_chunk_ref(secret, SECRET_MERGEACCS_START), (input.len() as u64).wrapping_mul(xxh64::PRIME_1));
@@ -1390,6 +2802,18 @@ fn xxh3_128_long_impl(input: &[u8], secret: &[u8]) -> u128 {
     lo as u128 | (hi as u128) << 64
 }
 
+#[inline]
+fn xxh3_128_long_impl(input: &[u8], secret: &[u8]) -> u128 {
+    #[cfg(not(feature = "runtime_dispatch"))]
+    {
+        xxh3_128_long_kernel::<NativeKernel>(input, secret)
+    }
+    #[cfg(feature = "runtime_dispatch")]
+    unsafe {
+        (dispatch::kernels().long_128)(input, secret)
+    }
+}
+
 #[inline(always)]
 fn xxh3_128_9to16(input: &[u8], seed: u64, secret: &[u8]) -> u128 {
     let flip_lo = (read_64le_unaligned(secret, 32) ^ read_64le_unaligned(secret, 40)).wrapping_sub(seed);
@@ -1615,6 +3039,40 @@ fn xxh3_128_long_with_secret(input: &[u8], _seed: u64, secret: &[u8]) -> u128 {
     xxh3_128_long_impl(input, secret)
 }
 
+///Generates secret of arbitrary length from provided seed material.
+///
+///Equivalent of `XXH3_generateSecret` from reference implementation, producing identical output.
+///`seed_material` can be of any length, including empty, in which case default secret is used as seed material.
+///
+///This function panics if `secret` doesn't fit minimum required secret size.
+pub fn generate_secret(secret: &mut [u8], seed_material: &[u8]) {
+    assert!(secret.len() >= SECRET_SIZE_MIN);
+
+    let seed_material = match seed_material.len() {
+        0 => &DEFAULT_SECRET[..],
+        _ => seed_material,
+    };
+
+    for chunk in secret.chunks_mut(seed_material.len()) {
+        chunk.copy_from_slice(&seed_material[..chunk.len()]);
+    }
+
+    #[inline(always)]
+    fn combine16(dst: &mut [u8], hash: u128) {
+        let value = u128::from_le_bytes(get_unaligned_chunk(dst, 0)) ^ hash;
+        dst[..16].copy_from_slice(&value.to_le_bytes());
+    }
+
+    let scrambler = xxh3_128_with_seed(seed_material, 0).to_be_bytes();
+    let nb_seg16 = secret.len() / 16;
+    for idx in 0..nb_seg16 {
+        combine16(&mut secret[idx * 16..], xxh3_128_with_seed(&scrambler, idx as u64));
+    }
+
+    let last_offset = secret.len() - 16;
+    combine16(&mut secret[last_offset..], u128::from_be_bytes(scrambler));
+}
+
 #[inline]
 ///Returns 128bit hash for provided input.
 pub fn xxh3_128(input: &[u8]) -> u128 {
@@ -1647,3 +3105,271 @@ pub fn xxh3_128_with_secret(input: &[u8], secret: &[u8]) -> u128 {
 pub fn xxh3_128_with_secret_input(input: &[u8], secret: &SecretInput<impl AsRef<[u8]>>) -> u128 {
     xxh3_128_internal(input, 0, secret.0.as_ref(), xxh3_128_long_with_secret)
 }
+
+#[inline]
+///Returns 128 hash for provided input using both custom secret and seed.
+///
+///Seed is used for inputs up to `MID_SIZE_MAX`, while secret is used for larger inputs,
+///which avoids cost of deriving secret from seed as in [xxh3_128_with_seed].
+///
+///This function panics if `secret` doesn't fit minimum required secret size.
+pub fn xxh3_128_with_secret_and_seed(input: &[u8], secret: &[u8], seed: u64) -> u128 {
+    assert!(secret.len() >= SECRET_SIZE_MIN);
+    if input.len() <= MID_SIZE_MAX {
+        xxh3_128_internal(input, seed, &DEFAULT_SECRET, xxh3_128_long_with_seed)
+    } else {
+        xxh3_128_long_with_secret(input, seed, secret)
+    }
+}
+
+pub mod scalar {
+    //!Portable `xxh3` implementation that never uses SIMD acceleration.
+    //!
+    //!Always compiled, regardless of target features, and produces the same output as functions in
+    //![xxh3](../index.html) module, allowing to cross-check SIMD kernels on a single machine.
+    //!
+    //!Prefer functions of parent module unless reference implementation is explicitly required.
+
+    use super::*;
+
+    #[inline(never)]
+    fn xxh3_64_long_default(input: &[u8], _seed: u64, _secret: &[u8]) -> u64 {
+        xxh3_64_long_kernel::<Scalar>(input, &DEFAULT_SECRET)
+    }
+
+    #[inline(never)]
+    fn xxh3_64_long_with_seed(input: &[u8], seed: u64, _secret: &[u8]) -> u64 {
+        match seed {
+            0 => xxh3_64_long_kernel::<Scalar>(input, &DEFAULT_SECRET),
+            seed => xxh3_64_long_kernel::<Scalar>(input, &custom_default_secret(seed)),
+        }
+    }
+
+    #[inline(never)]
+    fn xxh3_64_long_with_secret(input: &[u8], _seed: u64, secret: &[u8]) -> u64 {
+        xxh3_64_long_kernel::<Scalar>(input, secret)
+    }
+
+    #[inline(never)]
+    fn xxh3_128_long_default(input: &[u8], _seed: u64, _secret: &[u8]) -> u128 {
+        xxh3_128_long_kernel::<Scalar>(input, &DEFAULT_SECRET)
+    }
+
+    #[inline(never)]
+    fn xxh3_128_long_with_seed(input: &[u8], seed: u64, _secret: &[u8]) -> u128 {
+        match seed {
+            0 => xxh3_128_long_kernel::<Scalar>(input, &DEFAULT_SECRET),
+            seed => xxh3_128_long_kernel::<Scalar>(input, &custom_default_secret(seed)),
+        }
+    }
+
+    #[inline(never)]
+    fn xxh3_128_long_with_secret(input: &[u8], _seed: u64, secret: &[u8]) -> u128 {
+        xxh3_128_long_kernel::<Scalar>(input, secret)
+    }
+
+    #[inline]
+    ///Returns 64bit hash for provided input.
+    pub fn xxh3_64(input: &[u8]) -> u64 {
+        xxh3_64_internal(input, 0, &DEFAULT_SECRET, xxh3_64_long_default)
+    }
+
+    #[inline]
+    ///Returns 64bit hash for provided input using seed.
+    pub fn xxh3_64_with_seed(input: &[u8], seed: u64) -> u64 {
+        xxh3_64_internal(input, seed, &DEFAULT_SECRET, xxh3_64_long_with_seed)
+    }
+
+    #[inline]
+    ///Returns 64bit hash for provided input using custom secret.
+    ///
+    ///This function panics if `secret` doesn't fit minimum required secret size.
+    pub fn xxh3_64_with_secret(input: &[u8], secret: &[u8]) -> u64 {
+        assert!(secret.len() >= SECRET_SIZE_MIN);
+        xxh3_64_internal(input, 0, secret, xxh3_64_long_with_secret)
+    }
+
+    #[inline]
+    ///Returns 128bit hash for provided input.
+    pub fn xxh3_128(input: &[u8]) -> u128 {
+        xxh3_128_internal(input, 0, &DEFAULT_SECRET, xxh3_128_long_default)
+    }
+
+    #[inline]
+    ///Returns 128 hash for provided input using seed.
+    pub fn xxh3_128_with_seed(input: &[u8], seed: u64) -> u128 {
+        xxh3_128_internal(input, seed, &DEFAULT_SECRET, xxh3_128_long_with_seed)
+    }
+
+    #[inline]
+    ///Returns 128 hash for provided input using custom secret.
+    ///
+    ///This function panics if `secret` doesn't fit minimum required secret size.
+    pub fn xxh3_128_with_secret(input: &[u8], secret: &[u8]) -> u128 {
+        assert!(secret.len() >= SECRET_SIZE_MIN);
+        xxh3_128_internal(input, 0, secret, xxh3_128_long_with_secret)
+    }
+}
+
+#[cfg(feature = "std")]
+pub mod tree {
+    //!Tree hashing of large inputs, utilizing multiple threads.
+    //!
+    //!Input is split into chunks of `chunk_size` bytes, each hashed with [xxh3_128] into leaf.
+    //!Root hash is `xxh3_128` of concatenated leaves in canonical (big-endian) form, seeded with input length.
+    //!
+    //!Result depends only on input and `chunk_size`, hence [hash_parallel] and [hash_sequential] always agree,
+    //!regardless of number of threads.
+    //!Note that it is different from hashing whole input with `xxh3_128`.
+
+    use std::vec;
+    use std::thread;
+
+    use super::{xxh3_128, xxh3_128_with_seed};
+
+    const LEAF_SIZE: usize = 16;
+
+    #[inline]
+    fn leaves_len(input: &[u8], chunk_size: usize) -> usize {
+        assert!(chunk_size > 0, "chunk_size must be positive");
+        input.chunks(chunk_size).len() * LEAF_SIZE
+    }
+
+    fn hash_leaves(input: &[u8], chunk_size: usize, leaves: &mut [u8]) {
+        for (chunk, leaf) in input.chunks(chunk_size).zip(leaves.chunks_exact_mut(LEAF_SIZE)) {
+            leaf.copy_from_slice(&xxh3_128(chunk).to_be_bytes());
+        }
+    }
+
+    #[inline]
+    fn hash_root(input: &[u8], leaves: &[u8]) -> u128 {
+        xxh3_128_with_seed(leaves, input.len() as u64)
+    }
+
+    ///Returns tree hash of `input`, hashing chunks on current thread.
+    ///
+    ///This function panics if `chunk_size` is zero.
+    pub fn hash_sequential(input: &[u8], chunk_size: usize) -> u128 {
+        let mut leaves = vec![0u8; leaves_len(input, chunk_size)];
+        hash_leaves(input, chunk_size, &mut leaves);
+        hash_root(input, &leaves)
+    }
+
+    #[inline]
+    ///Returns tree hash of `input`, hashing chunks on all available cores.
+    ///
+    ///This function panics if `chunk_size` is zero.
+    pub fn hash_parallel(input: &[u8], chunk_size: usize) -> u128 {
+        let threads = thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1);
+        hash_parallel_with_threads(input, chunk_size, threads)
+    }
+
+    ///Returns tree hash of `input`, hashing chunks on up to `threads` threads, including current one.
+    ///
+    ///This function panics if `chunk_size` is zero.
+    pub fn hash_parallel_with_threads(input: &[u8], chunk_size: usize, threads: usize) -> u128 {
+        let mut leaves = vec![0u8; leaves_len(input, chunk_size)];
+        let chunks = leaves.len() / LEAF_SIZE;
+        if threads <= 1 || chunks <= 1 {
+            hash_leaves(input, chunk_size, &mut leaves);
+            return hash_root(input, &leaves);
+        }
+
+        //Each thread hashes contiguous range of chunks
+        let thread_chunks = (chunks - 1) / threads + 1;
+        thread::scope(|scope| {
+            let mut ranges = input.chunks(thread_chunks.saturating_mul(chunk_size)).zip(leaves.chunks_mut(thread_chunks * LEAF_SIZE));
+            let current = ranges.next();
+            for (input, leaves) in ranges {
+                scope.spawn(move || hash_leaves(input, chunk_size, leaves));
+            }
+            if let Some((input, leaves)) = current {
+                hash_leaves(input, chunk_size, leaves);
+            }
+        });
+
+        hash_root(input, &leaves)
+    }
+}
+
+#[cfg(feature = "rayon")]
+pub mod batch {
+    //!Parallel hashing of many independent inputs via `rayon`.
+    //!
+    //!Each output is equal to hashing corresponding input with one-shot function of parent module.
+
+    use std::vec::Vec;
+
+    use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, Map, MapWith, ParallelIterator};
+
+    use super::{xxh3_64, xxh3_64_with_seed, xxh3_128, xxh3_128_with_seed};
+
+    //Minimum number of inputs per task, amortizing scheduling cost over small inputs
+    const MIN_TASK_LEN: usize = 256;
+
+    #[inline]
+    ///Returns 64bit hash for each of `inputs`.
+    pub fn hash_many<T: AsRef<[u8]> + Sync>(inputs: &[T]) -> Vec<u64> {
+        inputs.par_iter().with_min_len(MIN_TASK_LEN).map(|input| xxh3_64(input.as_ref())).collect()
+    }
+
+    #[inline]
+    ///Returns 64bit hash for each of `inputs` using seed.
+    pub fn hash_many_with_seed<T: AsRef<[u8]> + Sync>(inputs: &[T], seed: u64) -> Vec<u64> {
+        inputs.par_iter().with_min_len(MIN_TASK_LEN).map(|input| xxh3_64_with_seed(input.as_ref(), seed)).collect()
+    }
+
+    #[inline]
+    ///Returns 128bit hash for each of `inputs`.
+    pub fn hash_many128<T: AsRef<[u8]> + Sync>(inputs: &[T]) -> Vec<u128> {
+        inputs.par_iter().with_min_len(MIN_TASK_LEN).map(|input| xxh3_128(input.as_ref())).collect()
+    }
+
+    #[inline]
+    ///Returns 128bit hash for each of `inputs` using seed.
+    pub fn hash_many128_with_seed<T: AsRef<[u8]> + Sync>(inputs: &[T], seed: u64) -> Vec<u128> {
+        inputs.par_iter().with_min_len(MIN_TASK_LEN).map(|input| xxh3_128_with_seed(input.as_ref(), seed)).collect()
+    }
+
+    ///Iterator returned by seeded methods of [ParallelHashExt].
+    pub type SeededMap<I, R> = MapWith<I, u64, fn(&mut u64, <I as ParallelIterator>::Item) -> R>;
+
+    ///Extension of `ParallelIterator` over byte inputs, mapping each input into its hash.
+    ///
+    ///```rust
+    ///use rayon::prelude::*;
+    ///use xxhash_rust::xxh3::batch::ParallelHashExt;
+    ///use xxhash_rust::xxh3::xxh3_64;
+    ///
+    ///let inputs = vec!["a", "b", "c"];
+    ///let hashes: Vec<u64> = inputs.par_iter().xxh3_64().collect();
+    ///assert_eq!(hashes[1], xxh3_64(b"b"));
+    ///```
+    pub trait ParallelHashExt: ParallelIterator where Self::Item: AsRef<[u8]> {
+        #[inline]
+        ///Maps each input into its 64bit hash.
+        fn xxh3_64(self) -> Map<Self, fn(Self::Item) -> u64> {
+            self.map(|input| xxh3_64(input.as_ref()))
+        }
+
+        #[inline]
+        ///Maps each input into its 64bit hash using seed.
+        fn xxh3_64_with_seed(self, seed: u64) -> SeededMap<Self, u64> {
+            self.map_with(seed, |seed, input| xxh3_64_with_seed(input.as_ref(), *seed))
+        }
+
+        #[inline]
+        ///Maps each input into its 128bit hash.
+        fn xxh3_128(self) -> Map<Self, fn(Self::Item) -> u128> {
+            self.map(|input| xxh3_128(input.as_ref()))
+        }
+
+        #[inline]
+        ///Maps each input into its 128bit hash using seed.
+        fn xxh3_128_with_seed(self, seed: u64) -> SeededMap<Self, u128> {
+            self.map_with(seed, |seed, input| xxh3_128_with_seed(input.as_ref(), *seed))
+        }
+    }
+
+    impl<I: ParallelIterator> ParallelHashExt for I where I::Item: AsRef<[u8]> {}
+}
@@ -37,12 +37,14 @@ pub const fn avalanche(mut value: u64) -> u64 {
     xorshift64(value, 32)
 }
 
+pub const STRONG_AVALANCHE_PRIME: u64 = 0x9FB21C651E98DF25;
+
 #[inline(always)]
 pub const fn strong_avalanche(mut value: u64, len: u64) -> u64 {
     value ^= value.rotate_left(49) ^ value.rotate_left(24);
-    value = value.wrapping_mul(0x9FB21C651E98DF25);
+    value = value.wrapping_mul(STRONG_AVALANCHE_PRIME);
     value ^= (value >> 35).wrapping_add(len);
-    value = value.wrapping_mul(0x9FB21C651E98DF25);
+    value = value.wrapping_mul(STRONG_AVALANCHE_PRIME);
     xorshift64(value, 28)
 }
 
@@ -6,6 +6,8 @@ use core::{mem, slice};
 
 use crate::utils::{Buffer, get_unaligned_chunk, get_aligned_chunk};
 use crate::xxh64_common::*;
+use crate::state::{StateReader, StateWriter};
+pub use crate::state::StateError;
 
 fn finalize(mut input: u64, mut data: &[u8], is_aligned: bool) -> u64 {
     let read_chunk = if is_aligned {
@@ -228,6 +230,240 @@ impl std::io::Write for Xxh64 {
     }
 }
 
+#[cfg(feature = "std")]
+const READ_BUFFER_SIZE: usize = CHUNK_SIZE * 512;
+
+#[cfg(feature = "std")]
+///Returns hash for content of `reader`, read until its end.
+pub fn hash_reader<R: std::io::Read>(reader: R, seed: u64) -> std::io::Result<u64> {
+    let mut hasher = Xxh64::new(seed);
+    let mut buffer = [0u8; READ_BUFFER_SIZE];
+    crate::utils::update_from_reader(reader, &mut buffer, |input| hasher.update(input))?;
+    Ok(hasher.digest())
+}
+
+#[cfg(feature = "std")]
+#[inline]
+///Returns hash for content of file at `path`.
+pub fn hash_file<P: AsRef<std::path::Path>>(path: P, seed: u64) -> std::io::Result<u64> {
+    hash_reader(std::fs::File::open(path)?, seed)
+}
+
+#[cfg(feature = "mmap")]
+///Returns hash for content of file at `path`, hashing memory-mapped file via [xxh64].
+///
+///Falls back to [hash_reader] for small files and files that cannot be mapped, such as pipes.
+///
+///Result is unspecified if file is modified while being hashed.
+pub fn hash_file_mmap<P: AsRef<std::path::Path>>(path: P, seed: u64) -> std::io::Result<u64> {
+    crate::utils::hash_file_mmap(path.as_ref(), |input| xxh64(input, seed), |file| hash_reader(file, seed))
+}
+
+impl Xxh64 {
+    const STATE_TAG: &'static [u8; 3] = b"X64";
+    ///Size of exported state.
+    pub const STATE_SIZE: usize = 85;
+
+    ///Exports state, allowing to resume hashing later via [Xxh64::from_bytes].
+    ///
+    ///Layout, following common header described in [state](../state/index.html) module:
+    ///
+    ///- `total_len` as `u64`;
+    ///- Size of buffered input as `u8`;
+    ///- 4 accumulators as `u64`;
+    ///- 32 bytes buffer, with unused bytes set to zero;
+    ///- `xxh64` checksum as `u64`.
+    pub fn to_bytes(&self) -> [u8; Self::STATE_SIZE] {
+        let mut result = [0; Self::STATE_SIZE];
+        let buffer = unsafe {
+            slice::from_raw_parts(self.mem.as_ptr() as *const u8, self.mem_size as usize)
+        };
+
+        let mut writer = StateWriter::new(&mut result, Self::STATE_TAG);
+        writer.write_u64(self.total_len);
+        writer.write_u8(self.mem_size as u8);
+        writer.write_u64(self.v.0);
+        writer.write_u64(self.v.1);
+        writer.write_u64(self.v.2);
+        writer.write_u64(self.v.3);
+        writer.write_bytes(buffer);
+        writer.write_bytes(&[0; CHUNK_SIZE][buffer.len()..]);
+        let checksum = xxh64(writer.written(), 0);
+        writer.write_u64(checksum);
+
+        result
+    }
+
+    //Checks that buffered size agrees with total length
+    fn validate_state(total_len: u64, mem_size: usize) -> Result<(), StateError> {
+        if mem_size as u64 != total_len % CHUNK_SIZE as u64 {
+            Err(StateError::InvalidState)
+        } else {
+            Ok(())
+        }
+    }
+
+    ///Restores state exported via [Xxh64::to_bytes].
+    pub fn from_bytes(bytes: &[u8; Self::STATE_SIZE]) -> Result<Self, StateError> {
+        let mut reader = StateReader::new(bytes, Self::STATE_TAG, mem::size_of::<u64>(), |content| xxh64(content, 0))?;
+        let total_len = reader.read_u64();
+        let mem_size = reader.read_u8() as usize;
+        let v = (reader.read_u64(), reader.read_u64(), reader.read_u64(), reader.read_u64());
+        let buffer = reader.read_bytes(CHUNK_SIZE);
+
+        if mem_size >= CHUNK_SIZE || buffer[mem_size..].iter().any(|byte| *byte != 0) {
+            return Err(StateError::InvalidState);
+        }
+        Self::validate_state(total_len, mem_size)?;
+
+        let mut result = Self::new(0);
+        result.total_len = total_len;
+        result.v = v;
+        Buffer {
+            ptr: result.mem.as_mut_ptr() as *mut u8,
+            len: mem::size_of_val(&result.mem),
+            offset: 0,
+        }.copy_from_slice(&buffer[..mem_size]);
+        result.mem_size = mem_size as u64;
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "Xxh64")]
+struct Xxh64State {
+    version: u8,
+    total_len: u64,
+    v: [u64; 4],
+    buffer: crate::serde_utils::ByteBuf<CHUNK_SIZE>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Xxh64 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let buffer = unsafe {
+            slice::from_raw_parts(self.mem.as_ptr() as *const u8, self.mem_size as usize)
+        };
+
+        Xxh64State {
+            version: crate::state::STATE_VERSION,
+            total_len: self.total_len,
+            v: [self.v.0, self.v.1, self.v.2, self.v.3],
+            buffer: crate::serde_utils::ByteBuf::new(buffer),
+        }.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Xxh64 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let state = Xxh64State::deserialize(deserializer)?;
+        let buffer = state.buffer.as_slice();
+        if state.version != crate::state::STATE_VERSION {
+            return Err(D::Error::custom(StateError::UnsupportedVersion));
+        }
+        Self::validate_state(state.total_len, buffer.len()).map_err(D::Error::custom)?;
+
+        let mut result = Self::new(0);
+        result.total_len = state.total_len;
+        result.v = (state.v[0], state.v[1], state.v[2], state.v[3]);
+        Buffer {
+            ptr: result.mem.as_mut_ptr() as *mut u8,
+            len: mem::size_of_val(&result.mem),
+            offset: 0,
+        }.copy_from_slice(buffer);
+        result.mem_size = buffer.len() as u64;
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::OutputSizeUser for Xxh64 {
+    type OutputSize = digest::consts::U8;
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for Xxh64 {
+    #[inline(always)]
+    fn update(&mut self, input: &[u8]) {
+        Xxh64::update(self, input)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutput for Xxh64 {
+    #[inline]
+    ///Writes hash in canonical big-endian form.
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.digest().to_be_bytes())
+    }
+}
+
+#[cfg(feature = "digest")]
+#[derive(Clone, Default)]
+///Wrapper over [Xxh64] retaining its seed, which allows to implement `digest::Reset` in addition to other `digest` traits
+pub struct Xxh64Digest {
+    hasher: Xxh64,
+    seed: u64,
+}
+
+#[cfg(feature = "digest")]
+impl Xxh64Digest {
+    #[inline(always)]
+    ///Creates new hasher with specified seed.
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            hasher: Xxh64::new(seed),
+            seed,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns seed used by hasher.
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    #[inline(always)]
+    ///Returns underlying hasher.
+    pub fn into_inner(self) -> Xxh64 {
+        self.hasher
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::OutputSizeUser for Xxh64Digest {
+    type OutputSize = digest::consts::U8;
+}
+
+#[cfg(feature = "digest")]
+impl digest::Update for Xxh64Digest {
+    #[inline(always)]
+    fn update(&mut self, input: &[u8]) {
+        self.hasher.update(input)
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::FixedOutput for Xxh64Digest {
+    #[inline]
+    ///Writes hash in canonical big-endian form.
+    fn finalize_into(self, out: &mut digest::Output<Self>) {
+        out.copy_from_slice(&self.hasher.digest().to_be_bytes())
+    }
+}
+
+#[cfg(feature = "digest")]
+impl digest::Reset for Xxh64Digest {
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.hasher.reset(self.seed)
+    }
+}
+
 impl Default for Xxh64 {
     #[inline(always)]
     fn default() -> Self {
@@ -80,6 +80,32 @@ fn assert_xxh32() {
     }
 }
 
+#[cfg(feature = "xxh32")]
+#[test]
+fn assert_xxh32_hasher() {
+    use core::hash::{BuildHasher, Hasher};
+    use xxhash_rust::xxh32::{xxh32, Xxh32, Xxh32Builder};
+
+    const BUILDER: Xxh32Builder = Xxh32Builder::new(1);
+    const HASHER: Xxh32 = BUILDER.build();
+
+    let mut hasher = HASHER;
+    hasher.write(b"input");
+    assert_eq!(hasher.finish(), xxh32(b"input", 1) as u64);
+    assert_eq!(BUILDER.hash_one(7u32), xxh32(&7u32.to_ne_bytes(), 1) as u64);
+    assert_eq!(BUILDER.build_hasher().finish(), xxh32(b"", 1) as u64);
+
+    let mut hasher = Xxh32::default();
+    hasher.write(b"input");
+    assert_eq!(hasher.finish(), xxh32(b"input", 0) as u64);
+
+    let mut map = std::collections::HashMap::with_hasher(Xxh32Builder::default());
+    for idx in 0..100u32 {
+        map.insert(idx, idx);
+    }
+    assert!((0..100u32).all(|idx| map[&idx] == idx));
+}
+
 #[cfg(feature = "const_xxh32")]
 #[cfg_attr(miri, ignore)]
 #[test]
@@ -183,6 +209,282 @@ fn assert_const_xxh3() {
     }
 }
 
+#[cfg(feature = "const_xxh3")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_const_xxh3_with_secret() {
+    use getrandom::fill as getrandom;
+    use xxhash_c_sys as sys;
+    use xxhash_rust::const_xxh3::{generate_secret, xxh3_64_with_secret, xxh3_128_with_secret};
+
+    const SECRET_MIN: [u8; 136] = generate_secret(b"firmware");
+    const SECRET_LARGE: [u8; 256] = generate_secret(b"firmware");
+    const HASH: u64 = xxh3_64_with_secret(b"input", &SECRET_MIN);
+    assert_eq!(HASH, unsafe {
+        sys::XXH3_64bits_withSecret(b"input".as_ptr() as _, 5, SECRET_MIN.as_ptr() as _, SECRET_MIN.len())
+    });
+
+    let mut input = Vec::with_capacity(2048);
+    for num in 0..input.capacity() {
+        input.resize(num, 1);
+        getrandom(&mut input).expect("getrandom");
+        let input = input.as_slice();
+        println!("input(len={})", input.len());
+
+        for secret in [&SECRET_MIN[..], &SECRET_LARGE[..]] {
+            let sys_result = unsafe {
+                sys::XXH3_64bits_withSecret(input.as_ptr() as _, input.len(), secret.as_ptr() as _, secret.len())
+            };
+            assert_eq!(xxh3_64_with_secret(input, secret), sys_result);
+
+            let sys_result128 = unsafe {
+                sys::XXH3_128bits_withSecret(input.as_ptr() as _, input.len(), secret.as_ptr() as _, secret.len())
+            };
+            let result128 = xxh3_128_with_secret(input, secret);
+            assert_eq!(result128 as u64, sys_result128.low64);
+            assert_eq!((result128 >> 64) as u64, sys_result128.high64);
+        }
+    }
+}
+
+#[cfg(all(feature = "const_xxh32", feature = "const_xxh64", feature = "const_xxh3"))]
+#[test]
+fn assert_const_macros() {
+    use xxhash_rust::{const_xxh32, const_xxh64, const_xxh3};
+
+    const SEED: u64 = 5;
+    const HASH: u64 = xxhash_rust::xxh3_64!("input");
+
+    let hash = match const_xxh3::xxh3_64(b"input") {
+        HASH => HASH,
+        _ => unreachable!(),
+    };
+    assert_eq!(hash, xxhash_rust::xxh3_64!("input"));
+    assert_eq!(xxhash_rust::xxh3_64!("input", SEED), const_xxh3::xxh3_64_with_seed(b"input", SEED));
+    assert_eq!(xxhash_rust::xxh3_128!("input"), const_xxh3::xxh3_128(b"input"));
+    assert_eq!(xxhash_rust::xxh3_128!("input", SEED), const_xxh3::xxh3_128_with_seed(b"input", SEED));
+    assert_eq!(xxhash_rust::xxh32!("input"), const_xxh32::xxh32(b"input", 0));
+    assert_eq!(xxhash_rust::xxh32!(concat!("in", "put"), 1), const_xxh32::xxh32(b"input", 1));
+    assert_eq!(xxhash_rust::xxh64!("input"), const_xxh64::xxh64(b"input", 0));
+    assert_eq!(xxhash_rust::xxh64!("input", SEED), const_xxh64::xxh64(b"input", SEED));
+
+    xxhash_rust::xxh3_64!(const GET = "get");
+    xxhash_rust::xxh3_64!(const SET = b"set");
+    xxhash_rust::xxh3_128!(const DEL = "del", SEED);
+    xxhash_rust::xxh32!(pub const PUT = "put", 1);
+    xxhash_rust::xxh64!(const POST = "post");
+    for (command, expected) in [("get", 0), ("set", 1), ("del", 2)] {
+        let index = match const_xxh3::xxh3_64(command.as_bytes()) {
+            GET => 0,
+            SET => 1,
+            _ => match const_xxh3::xxh3_128_with_seed(command.as_bytes(), SEED) {
+                DEL => 2,
+                _ => unreachable!(),
+            },
+        };
+        assert_eq!(index, expected);
+    }
+    assert!(matches!(const_xxh32::xxh32(b"put", 1), PUT));
+    assert!(matches!(const_xxh64::xxh64(b"post", 0), POST));
+
+    const BYTES: &[u8] = b"\xffinput";
+    assert_eq!(xxhash_rust::xxh3_64!(b"\xffinput"), const_xxh3::xxh3_64(BYTES));
+    assert_eq!(xxhash_rust::xxh3_128!(BYTES, SEED), const_xxh3::xxh3_128_with_seed(BYTES, SEED));
+    assert_eq!(xxhash_rust::xxh32!(b"\xffinput", 1), const_xxh32::xxh32(BYTES, 1));
+    assert_eq!(xxhash_rust::xxh64!(BYTES), const_xxh64::xxh64(BYTES, 0));
+}
+
+#[cfg(feature = "const_xxh3")]
+#[test]
+fn assert_const_xxh3_static_map() {
+    use core::convert::TryInto;
+    use xxhash_rust::const_xxh3::StaticMap;
+
+    static COMMANDS: StaticMap<&str, 4> = StaticMap::new([("get", "GET"), ("set", "SET"), ("del", "DEL"), ("", "EMPTY")]);
+    const EMPTY: StaticMap<u8, 0> = StaticMap::new([]);
+
+    assert_eq!(COMMANDS.len(), 4);
+    assert_eq!(COMMANDS.get("get"), Some(&"GET"));
+    assert_eq!(COMMANDS.get("set"), Some(&"SET"));
+    assert_eq!(COMMANDS.get("del"), Some(&"DEL"));
+    assert_eq!(COMMANDS.get(""), Some(&"EMPTY"));
+    assert_eq!(COMMANDS.get("put"), None);
+    assert!(!COMMANDS.contains_key("GET"));
+    assert_eq!(COMMANDS.iter().map(|entry| entry.0).collect::<Vec<_>>(), ["get", "set", "del", ""]);
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.get("get"), None);
+
+    let entries: Vec<(&'static str, Vec<usize>)> = (0..500).map(|idx| (&*Box::leak(format!("key-{}", idx).into_boxed_str()), vec![idx])).collect();
+    let entries: [(&'static str, Vec<usize>); 500] = entries.try_into().unwrap();
+    let map = StaticMap::new(entries);
+    for idx in 0..500 {
+        assert_eq!(map.get(&format!("key-{}", idx)), Some(&vec![idx]));
+        assert_eq!(map.get(&format!("key+{}", idx)), None);
+    }
+
+    let result = std::panic::catch_unwind(|| StaticMap::new([("get", 0), ("get", 1)]));
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "const_xxh3")]
+#[test]
+fn assert_const_xxh3_static_map_large() {
+    use xxhash_rust::const_xxh3::StaticMap;
+
+    const KEYS: usize = 500;
+    const KEY_LEN: usize = 3;
+    //Three lowercase letters per key: "aaa", "aab", ...
+    const KEY_BYTES: [u8; KEYS * KEY_LEN] = {
+        let mut bytes = [0u8; KEYS * KEY_LEN];
+        let mut idx = 0;
+        while idx < KEYS {
+            bytes[idx * KEY_LEN] = b'a' + (idx / 26 / 26 % 26) as u8;
+            bytes[idx * KEY_LEN + 1] = b'a' + (idx / 26 % 26) as u8;
+            bytes[idx * KEY_LEN + 2] = b'a' + (idx % 26) as u8;
+            idx += 1;
+        }
+        bytes
+    };
+
+    const fn entries() -> [(&'static str, usize); KEYS] {
+        let bytes: &'static [u8] = &KEY_BYTES;
+        let mut entries = [("", 0); KEYS];
+        let mut idx = 0;
+        while idx < KEYS {
+            let (key, _) = bytes.split_at(idx * KEY_LEN).1.split_at(KEY_LEN);
+            entries[idx] = match core::str::from_utf8(key) {
+                Ok(key) => (key, idx),
+                Err(_) => panic!("key is not utf-8"),
+            };
+            idx += 1;
+        }
+        entries
+    }
+
+    static MAP: StaticMap<usize, KEYS> = StaticMap::new(entries());
+
+    assert_eq!(MAP.len(), KEYS);
+    for (idx, key) in KEY_BYTES.chunks_exact(KEY_LEN).enumerate() {
+        let key = core::str::from_utf8(key).unwrap();
+        assert_eq!(MAP.get(key), Some(&idx));
+        assert_eq!(MAP.get(&key.to_uppercase()), None);
+    }
+}
+
+#[cfg(feature = "const_xxh32")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_const_xxh32_streaming() {
+    use getrandom::fill as getrandom;
+    use xxhash_c_sys as sys;
+    use xxhash_rust::const_xxh32::{xxh32, ConstXxh32};
+
+    const HASH: u32 = ConstXxh32::new(1).update(b"module::").update(b"").update(b"Type").digest();
+    assert_eq!(HASH, xxh32(b"module::Type", 1));
+
+    let mut input = Vec::with_capacity(1024);
+    for num in 0..input.capacity() {
+        input.resize(num, 1);
+        getrandom(&mut input).expect("getrandom");
+        println!("input(len={})", input.len());
+        let sys_result = unsafe {
+            sys::XXH32(input.as_ptr() as _, input.len(), 1)
+        };
+
+        for chunk_size in [1, 5, 16, 33, 512] {
+            let mut hasher = ConstXxh32::new(1);
+            for chunk in input.chunks(chunk_size) {
+                hasher = hasher.update(chunk);
+            }
+            assert_eq!(hasher.digest(), sys_result);
+        }
+    }
+}
+
+#[cfg(feature = "const_xxh64")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_const_xxh64_streaming() {
+    use getrandom::fill as getrandom;
+    use xxhash_c_sys as sys;
+    use xxhash_rust::const_xxh64::{xxh64, ConstXxh64};
+
+    const HASH: u64 = ConstXxh64::new(1).update(b"module::").update(b"").update(b"Type").digest();
+    assert_eq!(HASH, xxh64(b"module::Type", 1));
+
+    let mut input = Vec::with_capacity(1024);
+    for num in 0..input.capacity() {
+        input.resize(num, 1);
+        getrandom(&mut input).expect("getrandom");
+        println!("input(len={})", input.len());
+        let sys_result = unsafe {
+            sys::XXH64(input.as_ptr() as _, input.len(), 1)
+        };
+
+        for chunk_size in [1, 5, 32, 65, 512] {
+            let mut hasher = ConstXxh64::new(1);
+            for chunk in input.chunks(chunk_size) {
+                hasher = hasher.update(chunk);
+            }
+            assert_eq!(hasher.digest(), sys_result);
+        }
+    }
+}
+
+#[cfg(feature = "const_xxh3")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_const_xxh3_streaming() {
+    use getrandom::fill as getrandom;
+    use xxhash_c_sys as sys;
+    use xxhash_rust::const_xxh3::{xxh3_64, xxh3_128_with_seed, xxh3_64_with_secret, xxh3_128_with_secret, const_custom_default_secret, ConstXxh3};
+
+    const SECRET: [u8; 192] = const_custom_default_secret(5);
+
+    const HASH: u64 = ConstXxh3::new().update(b"module::").update(b"").update(b"Type").digest();
+    assert_eq!(HASH, xxh3_64(b"module::Type"));
+    const HASH128: u128 = ConstXxh3::with_seed(1).update(b"module::").update(b"Type").digest128();
+    assert_eq!(HASH128, xxh3_128_with_seed(b"module::Type", 1));
+
+    let mut input = Vec::with_capacity(2048);
+    for num in 0..input.capacity() {
+        input.resize(num, 1);
+        getrandom(&mut input).expect("getrandom");
+        println!("input(len={})", input.len());
+        let (sys_result, sys_result_seed) = unsafe {
+            (sys::XXH3_64bits(input.as_ptr() as _, input.len()), sys::XXH3_64bits_withSeed(input.as_ptr() as _, input.len(), 1))
+        };
+        let (sys_result128, sys_result128_seed) = unsafe {
+            (sys::XXH3_128bits(input.as_ptr() as _, input.len()), sys::XXH3_128bits_withSeed(input.as_ptr() as _, input.len(), 1))
+        };
+
+        for chunk_size in [7, 64, 100, 256, 1000] {
+            let mut hasher = ConstXxh3::new();
+            let mut hasher_seed = ConstXxh3::with_seed(1);
+            for chunk in input.chunks(chunk_size) {
+                hasher = hasher.update(chunk);
+                hasher_seed = hasher_seed.update(chunk);
+            }
+            assert_eq!(hasher.digest(), sys_result);
+            assert_eq!(hasher_seed.digest(), sys_result_seed);
+
+            let result128 = hasher.digest128();
+            assert_eq!(result128 as u64, sys_result128.low64);
+            assert_eq!((result128 >> 64) as u64, sys_result128.high64);
+            let result128 = hasher_seed.digest128();
+            assert_eq!(result128 as u64, sys_result128_seed.low64);
+            assert_eq!((result128 >> 64) as u64, sys_result128_seed.high64);
+        }
+
+        let mut hasher = ConstXxh3::with_secret(SECRET);
+        for chunk in input.chunks(100) {
+            hasher = hasher.update(chunk);
+        }
+        assert_eq!(hasher.digest(), xxh3_64_with_secret(&input, &SECRET));
+        assert_eq!(hasher.digest128(), xxh3_128_with_secret(&input, &SECRET));
+    }
+}
+
 #[cfg(feature = "xxh3")]
 #[cfg_attr(miri, ignore)]
 #[test]
@@ -285,3 +587,553 @@ fn xxh3_builder_seed_and_secret_are_order_independent() {
 
     assert_eq!(seed_then_secret.digest(), secret_then_seed.digest());
 }
+
+#[cfg(feature = "runtime_dispatch")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_xxh3_runtime_dispatch() {
+    use getrandom::fill as getrandom;
+    use xxhash_rust::xxh3::{xxh3_64, xxh3_128, xxh3_64_with_seed, xxh3_128_with_seed, Xxh3, Xxh3Default, Xxh3Backend};
+
+    const BACKENDS: [Xxh3Backend; 6] = [Xxh3Backend::Scalar, Xxh3Backend::Sse2, Xxh3Backend::Avx2, Xxh3Backend::Avx512, Xxh3Backend::Neon, Xxh3Backend::Simd128];
+
+    assert!(Xxh3Backend::Scalar.is_available());
+    assert!(Xxh3Backend::detect().is_available());
+
+    let mut input = Vec::with_capacity(4096);
+    for num in (0..input.capacity()).step_by(7) {
+        input.resize(num, 1);
+        getrandom(&mut input).expect("getrandom");
+        let input = input.as_slice();
+
+        assert!(Xxh3Backend::Scalar.force());
+        assert_eq!(Xxh3Backend::current(), Xxh3Backend::Scalar);
+        let expected = (xxh3_64(input), xxh3_128(input), xxh3_64_with_seed(input, 1), xxh3_128_with_seed(input, 1));
+
+        for backend in BACKENDS {
+            if !backend.force() {
+                assert!(!backend.is_available());
+                continue;
+            }
+            println!("input(len={}) backend={:?}", input.len(), backend);
+            assert_eq!(Xxh3Backend::current(), backend);
+
+            let mut hasher = Xxh3::new();
+            let mut hasher_seed = Xxh3::with_seed(1);
+            let mut hasher_default = Xxh3Default::new();
+            for chunk in input.chunks(97) {
+                hasher.update(chunk);
+                hasher_seed.update(chunk);
+                hasher_default.update(chunk);
+            }
+
+            assert_eq!(xxh3_64(input), expected.0);
+            assert_eq!(xxh3_128(input), expected.1);
+            assert_eq!(xxh3_64_with_seed(input, 1), expected.2);
+            assert_eq!(xxh3_128_with_seed(input, 1), expected.3);
+            assert_eq!(hasher.digest(), expected.0);
+            assert_eq!(hasher.digest128(), expected.1);
+            assert_eq!(hasher_default.digest(), expected.0);
+            assert_eq!(hasher_default.digest128(), expected.1);
+            assert_eq!(hasher_seed.digest(), expected.2);
+            assert_eq!(hasher_seed.digest128(), expected.3);
+        }
+    }
+
+    assert!(Xxh3Backend::detect().force());
+}
+
+#[cfg(feature = "xxh3")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_xxh3_scalar() {
+    use getrandom::fill as getrandom;
+    use xxhash_c_sys as sys;
+    use xxhash_rust::xxh3::{scalar, xxh3_64, xxh3_128, xxh3_64_with_seed, xxh3_128_with_seed, xxh3_64_with_secret, xxh3_128_with_secret};
+
+    let mut secret = [0u8; 256];
+    getrandom(&mut secret).expect("getrandom");
+
+    let mut input = Vec::with_capacity(2048);
+    for num in 0..input.capacity() {
+        input.resize(num, 1);
+        getrandom(&mut input).expect("getrandom");
+        let input = input.as_slice();
+        println!("input(len={})", input.len());
+
+        let sys_result = unsafe {
+            sys::XXH3_64bits(input.as_ptr() as _, input.len())
+        };
+        assert_eq!(scalar::xxh3_64(input), sys_result);
+        assert_eq!(scalar::xxh3_64(input), xxh3_64(input));
+        assert_eq!(scalar::xxh3_64_with_seed(input, 1), xxh3_64_with_seed(input, 1));
+        assert_eq!(scalar::xxh3_64_with_secret(input, &secret), xxh3_64_with_secret(input, &secret));
+
+        let sys_result128 = unsafe {
+            sys::XXH3_128bits(input.as_ptr() as _, input.len())
+        };
+        let result128 = scalar::xxh3_128(input);
+        assert_eq!(result128 as u64, sys_result128.low64);
+        assert_eq!((result128 >> 64) as u64, sys_result128.high64);
+        assert_eq!(result128, xxh3_128(input));
+        assert_eq!(scalar::xxh3_128_with_seed(input, 1), xxh3_128_with_seed(input, 1));
+        assert_eq!(scalar::xxh3_128_with_secret(input, &secret), xxh3_128_with_secret(input, &secret));
+    }
+}
+
+#[cfg(feature = "xxh3")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_xxh3_with_secret() {
+    use getrandom::fill as getrandom;
+    use xxhash_c_sys as sys;
+    use xxhash_rust::xxh3::{xxh3_64_with_secret, xxh3_128_with_secret, SecretInput, Xxh3WithSecret};
+
+    let mut secret = [0u8; 256];
+    getrandom(&mut secret).expect("getrandom");
+
+    for secret_len in [136, 192, 200, 256] {
+        let secret = &secret[..secret_len];
+        let mut hasher = Xxh3WithSecret::new(SecretInput::try_new(secret).expect("valid secret"));
+        let mut hasher_owned = Xxh3WithSecret::new(SecretInput::try_new(secret.to_vec()).expect("valid secret"));
+
+        let mut input = Vec::with_capacity(2048);
+        for num in 0..input.capacity() {
+            input.resize(num, 1);
+            getrandom(&mut input).expect("getrandom");
+            let input = input.as_slice();
+            println!("secret(len={}) input(len={})", secret.len(), input.len());
+
+            let sys_result = unsafe {
+                sys::XXH3_64bits_withSecret(input.as_ptr() as _, input.len(), secret.as_ptr() as _, secret.len())
+            };
+            let result = xxh3_64_with_secret(input, secret);
+            assert_eq!(result, sys_result);
+
+            let sys_result128 = unsafe {
+                sys::XXH3_128bits_withSecret(input.as_ptr() as _, input.len(), secret.as_ptr() as _, secret.len())
+            };
+            let result128 = xxh3_128_with_secret(input, secret);
+            assert_eq!(result128 as u64, sys_result128.low64);
+            assert_eq!((result128 >> 64) as u64, sys_result128.high64);
+
+            for chunk in input.chunks(100) {
+                hasher.update(chunk);
+            }
+            hasher_owned.update(input);
+            assert_eq!(hasher.digest(), result);
+            assert_eq!(hasher.digest128(), result128);
+            assert_eq!(hasher_owned.digest(), result);
+            assert_eq!(hasher_owned.digest128(), result128);
+
+            hasher.reset();
+            hasher_owned.reset();
+        }
+    }
+}
+
+#[cfg(feature = "xxh3")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_xxh3_with_secret_and_seed() {
+    use getrandom::fill as getrandom;
+    use xxhash_c_sys as sys;
+    use xxhash_rust::xxh3::{xxh3_64_with_secret_and_seed, xxh3_128_with_secret_and_seed, Xxh3};
+
+    let mut secret = [0u8; 192];
+    getrandom(&mut secret).expect("getrandom");
+    let mut seed = [0u8; 8];
+    getrandom(&mut seed).expect("getrandom");
+
+    for seed in [0, 1, u64::from_le_bytes(seed)] {
+        let mut hasher = Xxh3::with_secret_and_seed(secret, seed);
+
+        let mut input = Vec::with_capacity(2048);
+        for num in 0..input.capacity() {
+            input.resize(num, 1);
+            getrandom(&mut input).expect("getrandom");
+            let input = input.as_slice();
+            println!("seed={} input(len={})", seed, input.len());
+
+            let sys_result = unsafe {
+                sys::XXH3_64bits_withSecretandSeed(input.as_ptr() as _, input.len(), secret.as_ptr() as _, secret.len(), seed)
+            };
+            let result = xxh3_64_with_secret_and_seed(input, &secret, seed);
+            assert_eq!(result, sys_result);
+
+            let sys_result128 = unsafe {
+                sys::XXH3_128bits_withSecretandSeed(input.as_ptr() as _, input.len(), secret.as_ptr() as _, secret.len(), seed)
+            };
+            let result128 = xxh3_128_with_secret_and_seed(input, &secret, seed);
+            assert_eq!(result128 as u64, sys_result128.low64);
+            assert_eq!((result128 >> 64) as u64, sys_result128.high64);
+
+            for chunk in input.chunks(100) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.digest(), result);
+            assert_eq!(hasher.digest128(), result128);
+
+            hasher.reset();
+        }
+    }
+}
+
+#[cfg(feature = "xxh3")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_xxh3_batch() {
+    use getrandom::fill as getrandom;
+    use core::convert::TryInto;
+    use xxhash_rust::xxh3::{xxh3_64, xxh3_64_batch, xxh3_64_u64_batch};
+
+    let mut input = [0u8; 4096];
+    getrandom(&mut input).expect("getrandom");
+
+    //Mix of lengths, so that lanes are filled with keys from different positions
+    let keys: Vec<&[u8]> = (0..input.len() / 16).map(|idx| &input[idx..idx + (idx * 7) % 41]).collect();
+    for len in 0..keys.len() {
+        println!("keys(len={})", len);
+        let keys = &keys[..len];
+        let mut out = vec![0u64; len];
+        xxh3_64_batch(keys, &mut out);
+        for (key, result) in keys.iter().zip(out.iter()) {
+            assert_eq!(*result, xxh3_64(key));
+        }
+    }
+
+    //Groups limited to length ranges, hashed without dispatch on every key
+    for (min_len, max_len) in [(4, 8), (9, 16), (9, 32), (8, 32), (17, 128)] {
+        let keys: Vec<&[u8]> = (0..64).map(|idx| &input[idx * 3..idx * 3 + min_len + idx % (max_len - min_len + 1)]).collect();
+        let mut out = vec![0u64; keys.len()];
+        xxh3_64_batch(&keys, &mut out);
+        for (key, result) in keys.iter().zip(out.iter()) {
+            assert_eq!(*result, xxh3_64(key));
+        }
+    }
+
+    let keys: Vec<u64> = input.chunks_exact(8).map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap())).collect();
+    for len in 0..keys.len() {
+        let keys = &keys[..len];
+        let mut out = vec![0u64; len];
+        xxh3_64_u64_batch(keys, &mut out);
+        for (key, result) in keys.iter().zip(out.iter()) {
+            assert_eq!(*result, xxh3_64(&key.to_le_bytes()));
+        }
+    }
+
+    for key in [0, 1, u64::MAX, 0x8000_0000_0000_0000] {
+        let mut out = [0u64; 5];
+        xxh3_64_u64_batch(&[key; 5], &mut out);
+        assert_eq!(out, [xxh3_64(&key.to_le_bytes()); 5]);
+    }
+}
+
+#[cfg(feature = "xxh3")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_xxh3_int() {
+    use core::convert::TryInto;
+    use core::hash::Hasher;
+    use getrandom::fill as getrandom;
+    use xxhash_rust::xxh3::{hash_u32, hash_u32_with_seed, hash_u64, hash_u64_with_seed, hash_u128, hash_u128_with_seed};
+    use xxhash_rust::xxh3::{xxh3_64, xxh3_128, xxh3_64_with_seed, xxh3_64_with_secret, Xxh3, Xxh3Default};
+
+    let mut random = [0u8; 16 * 256];
+    getrandom(&mut random).expect("getrandom");
+    let mut secret = [0u8; 192];
+    getrandom(&mut secret).expect("getrandom");
+
+    for (idx, chunk) in random.chunks_exact(16).enumerate() {
+        let value = u128::from_le_bytes(chunk.try_into().unwrap());
+        let seed = match idx % 3 {
+            0 => 0,
+            1 => idx as u64,
+            _ => (value >> 32) as u64,
+        };
+        println!("value={} seed={}", value, seed);
+
+        assert_eq!(hash_u32(value as u32), xxh3_64(&(value as u32).to_le_bytes()));
+        assert_eq!(hash_u32_with_seed(value as u32, seed), xxh3_64_with_seed(&(value as u32).to_le_bytes(), seed));
+        assert_eq!(hash_u64(value as u64), xxh3_64(&(value as u64).to_le_bytes()));
+        assert_eq!(hash_u64_with_seed(value as u64, seed), xxh3_64_with_seed(&(value as u64).to_le_bytes(), seed));
+        assert_eq!(hash_u128(value), xxh3_64(&value.to_le_bytes()));
+        assert_eq!(hash_u128_with_seed(value, seed), xxh3_64_with_seed(&value.to_le_bytes(), seed));
+
+        //Hasher writes integers as native-endian bytes
+        let mut hasher = Xxh3Default::new();
+        hasher.write_u32(value as u32);
+        assert_eq!(hasher.finish(), xxh3_64(&(value as u32).to_ne_bytes()));
+        let mut hasher = Xxh3Default::new();
+        hasher.write_u64(value as u64);
+        assert_eq!(hasher.finish(), xxh3_64(&(value as u64).to_ne_bytes()));
+        let mut hasher = Xxh3Default::new();
+        hasher.write_u128(value);
+        assert_eq!(hasher.finish(), xxh3_64(&value.to_ne_bytes()));
+
+        let mut hasher = Xxh3::with_seed(seed);
+        hasher.write_u32(value as u32);
+        assert_eq!(hasher.finish(), xxh3_64_with_seed(&(value as u32).to_ne_bytes(), seed));
+        let mut hasher = Xxh3::with_seed(seed);
+        hasher.write_u64(value as u64);
+        assert_eq!(hasher.finish(), xxh3_64_with_seed(&(value as u64).to_ne_bytes(), seed));
+        let mut hasher = Xxh3::with_seed(seed);
+        hasher.write_u128(value);
+        assert_eq!(hasher.finish(), xxh3_64_with_seed(&value.to_ne_bytes(), seed));
+
+        let mut hasher = Xxh3::with_secret(secret);
+        hasher.write_u128(value);
+        assert_eq!(hasher.finish(), xxh3_64_with_secret(&value.to_ne_bytes(), &secret));
+
+        //Integers after other input go through regular update
+        let mut hasher = Xxh3Default::new();
+        hasher.write_u8(value as u8);
+        hasher.write_u16(value as u16);
+        hasher.write_usize(value as usize);
+        hasher.write(chunk);
+        hasher.write_u128(value);
+        let mut expected = Vec::new();
+        expected.push(value as u8);
+        expected.extend_from_slice(&(value as u16).to_ne_bytes());
+        expected.extend_from_slice(&(value as usize).to_ne_bytes());
+        expected.extend_from_slice(chunk);
+        expected.extend_from_slice(&value.to_ne_bytes());
+        assert_eq!(hasher.finish(), xxh3_64(&expected));
+        assert_eq!(hasher.digest128(), xxh3_128(&expected));
+    }
+
+    let mut hasher = Xxh3Default::new();
+    for idx in 0..100u64 {
+        hasher.write_u64(idx);
+    }
+    let expected: Vec<u8> = (0..100u64).flat_map(|idx| idx.to_ne_bytes()).collect();
+    assert_eq!(hasher.finish(), xxh3_64(&expected));
+}
+
+#[cfg(feature = "xxh3")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_xxh3_fast() {
+    use core::hash::{BuildHasher, Hasher};
+    use getrandom::fill as getrandom;
+    use xxhash_rust::xxh3::{xxh3_64_with_seed, xxh3_128_with_seed, Xxh3Fast, Xxh3FastBuilder};
+
+    let mut input = [0u8; 1024];
+    getrandom(&mut input).expect("getrandom");
+
+    for seed in [0, 1, u64::MAX] {
+        let mut hasher = Xxh3FastBuilder::new().with_seed(seed).build_hasher();
+        for len in [0, 1, 16, 128, 239, 240, 241, 255, 256, 257, 500, 1024] {
+            let input = &input[..len];
+            println!("seed={} input(len={})", seed, len);
+            for chunk_size in [1, 7, 240, 241, 256, 257, 1024] {
+                for chunk in input.chunks(chunk_size) {
+                    hasher.write(chunk);
+                }
+                assert_eq!(hasher.finish(), xxh3_64_with_seed(input, seed));
+                assert_eq!(hasher.digest128(), xxh3_128_with_seed(input, seed));
+                hasher.reset();
+            }
+        }
+    }
+
+    for split in [200, 300] {
+        let mut hasher = Xxh3Fast::new();
+        hasher.update(&input[..split]);
+        let mut cloned = hasher.clone();
+        hasher.update(&input[split..]);
+        cloned.update(&input[split..]);
+        assert_eq!(hasher.digest(), cloned.digest());
+    }
+
+    #[cfg(feature = "std")]
+    {
+        use xxhash_rust::xxh3::{Xxh3HashMap, Xxh3HashSet};
+
+        let mut map = Xxh3HashMap::default();
+        let mut set = Xxh3HashSet::default();
+        for idx in 0..1000u32 {
+            map.insert(idx.to_string(), idx);
+            set.insert(idx);
+        }
+        for idx in 0..1000u32 {
+            assert_eq!(map[&idx.to_string()], idx);
+            assert!(set.contains(&idx));
+        }
+    }
+}
+
+#[cfg(all(feature = "xxh3", feature = "std"))]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_xxh3_random_state() {
+    use core::hash::{BuildHasher, Hasher};
+    use std::collections::HashMap;
+    use xxhash_rust::xxh3::RandomXxh3State;
+
+    let input = [1u8; 1024];
+    let hash_with = |state: &RandomXxh3State, input: &[u8]| {
+        let mut hasher = state.build_hasher();
+        hasher.write(input);
+        hasher.finish()
+    };
+
+    let state = RandomXxh3State::new();
+    let other = RandomXxh3State::new();
+    for len in [0, 8, 240, 1024] {
+        assert_eq!(hash_with(&state, &input[..len]), hash_with(&state, &input[..len]));
+        assert_eq!(hash_with(&state.clone(), &input[..len]), hash_with(&state, &input[..len]));
+        assert_ne!(hash_with(&state, &input[..len]), hash_with(&other, &input[..len]));
+    }
+
+    let thread_state = std::thread::spawn(RandomXxh3State::new).join().unwrap();
+    assert_ne!(hash_with(&thread_state, &input), hash_with(&state, &input));
+    assert_eq!(format!("{:?}", state), "RandomXxh3State { .. }");
+
+    let mut map = HashMap::with_hasher(RandomXxh3State::default());
+    for idx in 0..1000u32 {
+        map.insert(idx, idx.to_string());
+    }
+    for idx in 0..1000u32 {
+        assert_eq!(map[&idx], idx.to_string());
+    }
+}
+
+#[cfg(any(feature = "xxh3", feature = "const_xxh3"))]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_xxh3_generate_secret() {
+    use getrandom::fill as getrandom;
+    use xxhash_c_sys as sys;
+
+    fn sys_generate_secret(secret: &mut [u8], seed_material: &[u8]) {
+        let result = unsafe {
+            sys::XXH3_generateSecret(secret.as_mut_ptr() as _, secret.len(), seed_material.as_ptr() as _, seed_material.len())
+        };
+        assert_eq!(result, sys::XXH_errorcode_XXH_OK);
+    }
+
+    let mut seed_material = Vec::with_capacity(512);
+    for num in 0..seed_material.capacity() {
+        seed_material.resize(num, 1);
+        getrandom(&mut seed_material).expect("getrandom");
+        println!("seed_material(len={})", seed_material.len());
+
+        let mut expected = [0u8; 256];
+        sys_generate_secret(&mut expected, &seed_material);
+
+        #[cfg(feature = "xxh3")]
+        {
+            let mut secret = [0u8; 256];
+            xxhash_rust::xxh3::generate_secret(&mut secret, &seed_material);
+            assert_eq!(secret, expected);
+
+            let mut expected = [0u8; 137];
+            sys_generate_secret(&mut expected, &seed_material);
+            let mut secret = [0u8; 137];
+            xxhash_rust::xxh3::generate_secret(&mut secret, &seed_material);
+            assert_eq!(secret, expected);
+        }
+
+        #[cfg(feature = "const_xxh3")]
+        {
+            let secret: [u8; 256] = xxhash_rust::const_xxh3::generate_secret(&seed_material);
+            assert_eq!(secret, expected);
+
+            let mut expected = [0u8; 137];
+            sys_generate_secret(&mut expected, &seed_material);
+            let secret: [u8; 137] = xxhash_rust::const_xxh3::generate_secret(&seed_material);
+            assert_eq!(secret, expected);
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_canonical() {
+    use getrandom::fill as getrandom;
+    use xxhash_c_sys as sys;
+    use xxhash_rust::canonical::{Xxh32Canonical, Xxh64Canonical, Xxh128Canonical, ParseCanonicalError};
+
+    let mut input = [0u8; 16];
+    for _ in 0..1024 {
+        getrandom(&mut input).expect("getrandom");
+        let hash32 = u32::from_le_bytes([input[0], input[1], input[2], input[3]]);
+        let hash64 = u64::from_le_bytes([input[0], input[1], input[2], input[3], input[4], input[5], input[6], input[7]]);
+        let hash128 = u128::from_le_bytes(input);
+
+        let mut sys32 = sys::XXH32_canonical_t { digest: [0; 4] };
+        let mut sys64 = sys::XXH64_canonical_t { digest: [0; 8] };
+        let mut sys128 = sys::XXH128_canonical_t { digest: [0; 16] };
+        unsafe {
+            sys::XXH32_canonicalFromHash(&mut sys32, hash32);
+            sys::XXH64_canonicalFromHash(&mut sys64, hash64);
+            sys::XXH128_canonicalFromHash(&mut sys128, sys::XXH128_hash_t { low64: hash128 as u64, high64: (hash128 >> 64) as u64 });
+        }
+
+        let canonical32 = Xxh32Canonical::from_hash(hash32);
+        assert_eq!(canonical32.as_ref(), sys32.digest);
+        assert_eq!(canonical32.to_hash(), hash32);
+        assert_eq!(canonical32.to_string(), format!("{:08x}", hash32));
+        assert_eq!(canonical32.to_string().to_uppercase().parse::<Xxh32Canonical>(), Ok(canonical32));
+
+        let canonical64 = Xxh64Canonical::from_hash(hash64);
+        assert_eq!(canonical64.as_ref(), sys64.digest);
+        assert_eq!(canonical64.to_hash(), hash64);
+        assert_eq!(canonical64.to_string(), format!("{:016x}", hash64));
+        assert_eq!(canonical64.to_string().parse::<Xxh64Canonical>(), Ok(canonical64));
+
+        let canonical128 = Xxh128Canonical::from_hash(hash128);
+        assert_eq!(canonical128.as_ref(), sys128.digest);
+        assert_eq!(canonical128.to_hash(), hash128);
+        assert_eq!(canonical128.to_string(), format!("{:032x}", hash128));
+        assert_eq!(canonical128.to_string().parse::<Xxh128Canonical>(), Ok(canonical128));
+    }
+
+    assert_eq!("0123456".parse::<Xxh32Canonical>(), Err(ParseCanonicalError::InvalidLength));
+    assert_eq!("0123456g".parse::<Xxh32Canonical>(), Err(ParseCanonicalError::InvalidDigit));
+    assert_eq!("+0123456".parse::<Xxh32Canonical>(), Err(ParseCanonicalError::InvalidDigit));
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn assert_xxh128_hash() {
+    use core::convert::TryInto;
+    use core::cmp::Ordering;
+    use getrandom::fill as getrandom;
+    use xxhash_c_sys as sys;
+    use xxhash_rust::canonical::{Xxh128Canonical, Xxh128Hash};
+
+    let mut input = [0u8; 32];
+    for idx in 0..1024 {
+        getrandom(&mut input).expect("getrandom");
+        let left = u128::from_le_bytes(input[..16].try_into().unwrap());
+        let mut right = u128::from_le_bytes(input[16..].try_into().unwrap());
+        //Exercise equal halves too
+        match idx % 3 {
+            0 => right = (right & !(u64::MAX as u128)) | (left & u64::MAX as u128),
+            1 => right = (left & !(u64::MAX as u128)) | (right & u64::MAX as u128),
+            _ => (),
+        }
+
+        let left_hash = Xxh128Hash::from(left);
+        let right_hash = Xxh128Hash::from(right);
+        assert_eq!(left_hash.low64(), left as u64);
+        assert_eq!(left_hash.high64(), (left >> 64) as u64);
+        assert_eq!(u128::from(left_hash), left);
+        assert_eq!(Xxh128Hash::new(left as u64, (left >> 64) as u64), left_hash);
+
+        let sys_left = sys::XXH128_hash_t { low64: left_hash.low64(), high64: left_hash.high64() };
+        let sys_right = sys::XXH128_hash_t { low64: right_hash.low64(), high64: right_hash.high64() };
+        let sys_cmp = unsafe {
+            sys::XXH128_cmp(&sys_left as *const _ as _, &sys_right as *const _ as _)
+        };
+        assert_eq!(left_hash.cmp(&right_hash), sys_cmp.cmp(&0));
+        assert_eq!(left_hash.cmp(&left_hash), Ordering::Equal);
+        assert_eq!(left_hash == right_hash, unsafe { sys::XXH128_isEqual(sys_left, sys_right) } != 0);
+
+        let canonical = left_hash.to_canonical();
+        assert_eq!(canonical, Xxh128Canonical::from_hash(left));
+        assert_eq!(Xxh128Hash::from_canonical(&canonical), left_hash);
+    }
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "rayon")]
+
+use getrandom::fill as getrandom;
+use rayon::prelude::*;
+use xxhash_rust::xxh3::batch::{hash_many, hash_many128, hash_many128_with_seed, hash_many_with_seed, ParallelHashExt};
+use xxhash_rust::xxh3::{xxh3_64, xxh3_64_with_seed, xxh3_128, xxh3_128_with_seed};
+
+fn random_inputs() -> Vec<Vec<u8>> {
+    let mut input = [0u8; 1024];
+    getrandom(&mut input).expect("getrandom");
+    (0..10_000).map(|idx| input[..idx % input.len()].to_vec()).collect()
+}
+
+#[test]
+fn batch_hash_many() {
+    let inputs = random_inputs();
+
+    assert_eq!(hash_many(&inputs), inputs.iter().map(|input| xxh3_64(input)).collect::<Vec<_>>());
+    assert_eq!(hash_many_with_seed(&inputs, 1), inputs.iter().map(|input| xxh3_64_with_seed(input, 1)).collect::<Vec<_>>());
+    assert_eq!(hash_many128(&inputs), inputs.iter().map(|input| xxh3_128(input)).collect::<Vec<_>>());
+    assert_eq!(hash_many128_with_seed(&inputs, 1), inputs.iter().map(|input| xxh3_128_with_seed(input, 1)).collect::<Vec<_>>());
+
+    let slices: Vec<&[u8]> = inputs.iter().map(|input| input.as_slice()).collect();
+    assert_eq!(hash_many(&slices), hash_many(&inputs));
+    assert!(hash_many::<&[u8]>(&[]).is_empty());
+}
+
+#[test]
+fn batch_parallel_iterator() {
+    let inputs = random_inputs();
+
+    assert_eq!(inputs.par_iter().xxh3_64().collect::<Vec<_>>(), hash_many(&inputs));
+    assert_eq!(inputs.par_iter().xxh3_64_with_seed(1).collect::<Vec<_>>(), hash_many_with_seed(&inputs, 1));
+    assert_eq!(inputs.par_iter().xxh3_128().collect::<Vec<_>>(), hash_many128(&inputs));
+    let expected = hash_many128_with_seed(&inputs, 1);
+    assert_eq!(inputs.into_par_iter().xxh3_128_with_seed(1).collect::<Vec<_>>(), expected);
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "checksum")]
+
+use std::path::PathBuf;
+
+use xxhash_rust::checksum::{Algorithm, ChecksumLine, Format, LineStatus, Report, Verifier};
+use xxhash_c_sys as sys;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("checksum-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+#[test]
+fn checksum_hash_reader() {
+    let input = b"checksum test input";
+
+    let (xxh32, xxh64, xxh3_64, xxh3_128) = unsafe {
+        let xxh128 = sys::XXH3_128bits(input.as_ptr() as _, input.len());
+        (
+            sys::XXH32(input.as_ptr() as _, input.len(), 0),
+            sys::XXH64(input.as_ptr() as _, input.len(), 0),
+            sys::XXH3_64bits(input.as_ptr() as _, input.len()),
+            (xxh128.high64 as u128) << 64 | xxh128.low64 as u128,
+        )
+    };
+
+    assert_eq!(Algorithm::Xxh32.hash_reader(&input[..]).unwrap(), xxh32 as u128);
+    assert_eq!(Algorithm::Xxh64.hash_reader(&input[..]).unwrap(), xxh64 as u128);
+    assert_eq!(Algorithm::Xxh3_64.hash_reader(&input[..]).unwrap(), xxh3_64 as u128);
+    assert_eq!(Algorithm::Xxh3_128.hash_reader(&input[..]).unwrap(), xxh3_128);
+}
+
+#[test]
+fn checksum_line_format() {
+    let cases = [
+        (Algorithm::Xxh32, 0x0123_4567, "01234567  file", "XXH32 (file) = 01234567"),
+        (Algorithm::Xxh64, 0x0123_4567_89ab_cdef, "0123456789abcdef  file", "XXH64 (file) = 0123456789abcdef"),
+        (Algorithm::Xxh3_64, 0x0123_4567_89ab_cdef, "XXH3_0123456789abcdef  file", "XXH3 (file) = 0123456789abcdef"),
+        (Algorithm::Xxh3_128, 0x0123_4567_89ab_cdef_0123_4567_89ab_cdef, "0123456789abcdef0123456789abcdef  file", "XXH128 (file) = 0123456789abcdef0123456789abcdef"),
+    ];
+
+    for (algorithm, hash, gnu, bsd) in cases {
+        let line = ChecksumLine::new(algorithm, hash, "file".into());
+        assert_eq!(line.to_string(), gnu);
+        assert_eq!(gnu.parse::<ChecksumLine>().unwrap(), line);
+
+        let line = line.with_format(Format::Bsd);
+        assert_eq!(line.to_string(), bsd);
+        assert_eq!(bsd.parse::<ChecksumLine>().unwrap(), line);
+    }
+
+    let line = ChecksumLine::new(Algorithm::Xxh32, 1, "a\\b\nc (d) = e".into());
+    assert_eq!(line.to_string(), "\\00000001  a\\\\b\\nc (d) = e");
+    assert_eq!(line.to_string().parse::<ChecksumLine>().unwrap(), line);
+    let mut status = Vec::new();
+    line.write_status(false, &mut status).unwrap();
+    assert_eq!(status, b"\\a\\\\b\\nc (d) = e: FAILED");
+
+    let line = line.with_format(Format::Bsd);
+    assert_eq!(line.to_string(), "\\XXH32 (a\\\\b\\nc (d) = e) = 00000001");
+    assert_eq!(line.to_string().parse::<ChecksumLine>().unwrap(), line);
+
+    let line = "0123456789ABCDEF *binary".parse::<ChecksumLine>().unwrap();
+    assert_eq!(line.hash, 0x0123_4567_89ab_cdef);
+    assert_eq!(line.file_name, "binary");
+
+    for improper in ["", "01234567", "01234567 file", "0123456  file", "XXH3_01234567  file", "0123456g  file", "01234567  ", "\\01234567  a\\b", "XXH3 (file) = 01234567", "XXH3 (file)"] {
+        assert!(improper.parse::<ChecksumLine>().is_err(), "{:?}", improper);
+    }
+}
+
+#[test]
+fn checksum_verifier() {
+    let dir = temp_dir("verifier");
+    let first = dir.join("first");
+    let second = dir.join("second");
+    std::fs::write(&first, b"first").unwrap();
+    std::fs::write(&second, b"second").unwrap();
+
+    let mut checksums = String::new();
+    for algorithm in [Algorithm::Xxh32, Algorithm::Xxh64, Algorithm::Xxh3_64, Algorithm::Xxh3_128] {
+        for path in [&first, &second] {
+            let hash = algorithm.hash_reader(std::fs::File::open(path).unwrap()).unwrap();
+            let line = ChecksumLine::new(algorithm, hash, path.clone().into_os_string());
+            checksums.push_str(&format!("{}\n{}\r\n", line, line.clone().with_format(Format::Bsd)));
+        }
+    }
+
+    let report = Verifier::new(checksums.as_bytes()).finish().unwrap();
+    assert_eq!(report, Report { properly_formatted: 16, matched: 16, ..Report::default() });
+    assert!(report.is_success(true));
+    assert_eq!(report.to_string(), "");
+
+    std::fs::write(&second, b"changed").unwrap();
+    checksums.push_str("improper line\n");
+    checksums.push_str(&format!("{}\n", ChecksumLine::new(Algorithm::Xxh64, 0, dir.join("missing").into_os_string())));
+
+    let mut verifier = Verifier::new(checksums.as_bytes());
+    for result in &mut verifier {
+        let result = result.unwrap();
+        match (result.line_number, result.status) {
+            (1..=16, LineStatus::Ok(line)) => assert_eq!(line.file_name, first.to_str().unwrap()),
+            (1..=16, LineStatus::Failed(line)) => assert_eq!(line.file_name, second.to_str().unwrap()),
+            (17, LineStatus::ImproperlyFormatted) => (),
+            (18, LineStatus::ReadFailure(line, _)) => assert_eq!(line.hash, 0),
+            (line_number, status) => panic!("line {}: unexpected {:?}", line_number, status),
+        }
+    }
+    let report = *verifier.report();
+    assert_eq!(report, Report { properly_formatted: 17, improperly_formatted: 1, read_failures: 1, matched: 8, mismatched: 8 });
+    assert!(!report.is_success(false));
+    assert_eq!(report.to_string(), "1 line is improperly formatted\n1 listed file could not be read\n8 computed checksums did NOT match\n");
+
+    let report = Verifier::new(&b"improper line\n"[..]).finish().unwrap();
+    assert!(!report.is_success(false));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn checksum_non_utf8_file_name() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = temp_dir("non-utf8");
+    let file_name = dir.join(OsStr::from_bytes(b"invalid\xff\\name"));
+    std::fs::write(&file_name, b"content").unwrap();
+
+    let hash = Algorithm::Xxh64.hash_reader(std::fs::File::open(&file_name).unwrap()).unwrap();
+    let line = ChecksumLine::new(Algorithm::Xxh64, hash, file_name.clone().into_os_string());
+    let mut bytes = Vec::new();
+    line.write_to(&mut bytes).unwrap();
+
+    let mut expected = format!("\\{:016x}  ", hash).into_bytes();
+    expected.extend_from_slice(dir.as_os_str().as_bytes());
+    expected.extend_from_slice(b"/invalid\xff\\\\name");
+    assert_eq!(bytes, expected);
+    assert_eq!(ChecksumLine::from_bytes(&bytes).unwrap(), line);
+
+    bytes.push(b'\n');
+    let report = Verifier::new(bytes.as_slice()).finish().unwrap();
+    assert_eq!(report, Report { properly_formatted: 1, matched: 1, ..Report::default() });
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "digest")]
+
+use digest::{FixedOutput, Reset, Update};
+use getrandom::fill as getrandom;
+
+//Hashes input via generic digest traits
+fn hash_generic<D: Update + FixedOutput>(mut hasher: D, input: &[u8]) -> Vec<u8> {
+    for chunk in input.chunks(100) {
+        hasher.update(chunk);
+    }
+    hasher.finalize_fixed().to_vec()
+}
+
+//Hashes input via generic digest traits, checking reset restores initial state
+fn hash_generic_reset<D: Update + FixedOutput + Reset>(mut hasher: D, input: &[u8]) -> Vec<u8> {
+    hasher.update(b"garbage");
+    Reset::reset(&mut hasher);
+    hash_generic(hasher, input)
+}
+
+#[test]
+fn digest_traits() {
+    let mut input = Vec::with_capacity(1024);
+    for num in 0..input.capacity() {
+        input.resize(num, 1);
+        getrandom(&mut input).expect("getrandom");
+        let input = input.as_slice();
+
+        #[cfg(feature = "xxh32")]
+        {
+            use xxhash_rust::xxh32::{xxh32, Xxh32, Xxh32Digest};
+            assert_eq!(hash_generic(Xxh32::new(5), input), xxh32(input, 5).to_be_bytes());
+            assert_eq!(hash_generic_reset(Xxh32Digest::new(5), input), xxh32(input, 5).to_be_bytes());
+        }
+
+        #[cfg(feature = "xxh64")]
+        {
+            use xxhash_rust::xxh64::{xxh64, Xxh64, Xxh64Digest};
+            assert_eq!(hash_generic(Xxh64::new(5), input), xxh64(input, 5).to_be_bytes());
+            assert_eq!(hash_generic_reset(Xxh64Digest::new(5), input), xxh64(input, 5).to_be_bytes());
+        }
+
+        #[cfg(feature = "xxh3")]
+        {
+            use xxhash_rust::xxh3::{xxh3_64, xxh3_64_with_seed, xxh3_128_with_seed, Xxh3, Xxh3Default, Xxh128};
+            assert_eq!(hash_generic_reset(Xxh3Default::new(), input), xxh3_64(input).to_be_bytes());
+            assert_eq!(hash_generic_reset(Xxh3::with_seed(5), input), xxh3_64_with_seed(input, 5).to_be_bytes());
+            assert_eq!(hash_generic_reset(Xxh128(Xxh3::with_seed(5)), input), xxh3_128_with_seed(input, 5).to_be_bytes());
+        }
+    }
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "mmap")]
+
+use std::path::PathBuf;
+
+use getrandom::fill as getrandom;
+
+const LENS: [usize; 6] = [0, 1, 16383, 16384, 16385, 1024 * 1024 + 7];
+
+fn temp_file(name: &str, input: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mmap-{}-{}-{}", name, input.len(), std::process::id()));
+    std::fs::write(&path, input).expect("write temp file");
+    path
+}
+
+fn random_input() -> Vec<u8> {
+    let mut input = vec![0u8; LENS[LENS.len() - 1]];
+    getrandom(&mut input).expect("getrandom");
+    input
+}
+
+#[cfg(feature = "xxh3")]
+#[test]
+fn mmap_xxh3() {
+    use xxhash_rust::xxh3::{hash_file, hash_file128, hash_file_mmap, hash_file128_mmap, xxh3_64, xxh3_128};
+
+    let input = random_input();
+    for len in LENS {
+        let input = &input[..len];
+        let path = temp_file("xxh3", input);
+        assert_eq!(hash_file_mmap(&path).unwrap(), xxh3_64(input));
+        assert_eq!(hash_file_mmap(&path).unwrap(), hash_file(&path).unwrap());
+        assert_eq!(hash_file128_mmap(&path).unwrap(), xxh3_128(input));
+        assert_eq!(hash_file128_mmap(&path).unwrap(), hash_file128(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert!(hash_file_mmap(&path).is_err());
+    }
+
+    #[cfg(unix)]
+    assert_eq!(hash_file_mmap("/dev/null").unwrap(), xxh3_64(&[]));
+}
+
+#[cfg(feature = "xxh64")]
+#[test]
+fn mmap_xxh64() {
+    use xxhash_rust::xxh64::{hash_file, hash_file_mmap, xxh64};
+
+    let input = random_input();
+    for len in LENS {
+        let input = &input[..len];
+        let path = temp_file("xxh64", input);
+        assert_eq!(hash_file_mmap(&path, 1).unwrap(), xxh64(input, 1));
+        assert_eq!(hash_file_mmap(&path, 0).unwrap(), hash_file(&path, 0).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert!(hash_file_mmap(&path, 0).is_err());
+    }
+
+    #[cfg(unix)]
+    assert_eq!(hash_file_mmap("/dev/null", 1).unwrap(), xxh64(&[], 1));
+}
+
+#[cfg(feature = "xxh32")]
+#[test]
+fn mmap_xxh32() {
+    use xxhash_rust::xxh32::{hash_file, hash_file_mmap, xxh32};
+
+    let input = random_input();
+    for len in LENS {
+        let input = &input[..len];
+        let path = temp_file("xxh32", input);
+        assert_eq!(hash_file_mmap(&path, 1).unwrap(), xxh32(input, 1));
+        assert_eq!(hash_file_mmap(&path, 0).unwrap(), hash_file(&path, 0).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert!(hash_file_mmap(&path, 0).is_err());
+    }
+
+    #[cfg(unix)]
+    assert_eq!(hash_file_mmap("/dev/null", 1).unwrap(), xxh32(&[], 1));
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "std")]
+
+use std::io::{self, Read};
+
+use getrandom::fill as getrandom;
+
+//Returns at most `max_read` bytes per read, interrupting every other read
+struct ChunkedReader<'a> {
+    input: &'a [u8],
+    max_read: usize,
+    is_interrupted: bool,
+}
+
+impl<'a> ChunkedReader<'a> {
+    fn new(input: &'a [u8], max_read: usize) -> Self {
+        Self {
+            input,
+            max_read,
+            is_interrupted: false,
+        }
+    }
+}
+
+impl Read for ChunkedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.is_interrupted = !self.is_interrupted;
+        if self.is_interrupted {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+
+        let size = buf.len().min(self.max_read).min(self.input.len());
+        buf[..size].copy_from_slice(&self.input[..size]);
+        self.input = &self.input[size..];
+        Ok(size)
+    }
+}
+
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::ErrorKind::UnexpectedEof.into())
+    }
+}
+
+const LENS: [usize; 10] = [0, 1, 63, 255, 256, 257, 4096, 16384, 16385, 40000];
+const MAX_READS: [usize; 4] = [1, 100, 256, usize::MAX];
+
+#[cfg(feature = "xxh3")]
+#[test]
+fn reader_xxh3() {
+    use xxhash_rust::xxh3::{hash_file, hash_file128, hash_reader, hash_reader128, xxh3_64, xxh3_128};
+
+    let mut input = vec![0u8; 40000];
+    getrandom(&mut input).expect("getrandom");
+
+    for len in LENS {
+        let input = &input[..len];
+        assert_eq!(hash_reader(input).unwrap(), xxh3_64(input));
+        assert_eq!(hash_reader128(input).unwrap(), xxh3_128(input));
+        for max_read in MAX_READS {
+            assert_eq!(hash_reader(ChunkedReader::new(input, max_read)).unwrap(), xxh3_64(input));
+            assert_eq!(hash_reader128(ChunkedReader::new(input, max_read)).unwrap(), xxh3_128(input));
+        }
+    }
+
+    assert!(hash_reader(FailingReader).is_err());
+    assert!(hash_reader128(FailingReader).is_err());
+
+    let path = std::env::temp_dir().join(format!("reader-xxh3-{}", std::process::id()));
+    std::fs::write(&path, &input).unwrap();
+    assert_eq!(hash_file(&path).unwrap(), xxh3_64(&input));
+    assert_eq!(hash_file128(&path).unwrap(), xxh3_128(&input));
+    std::fs::remove_file(&path).unwrap();
+    assert!(hash_file(&path).is_err());
+}
+
+#[cfg(feature = "xxh64")]
+#[test]
+fn reader_xxh64() {
+    use xxhash_rust::xxh64::{hash_file, hash_reader, xxh64};
+
+    let mut input = vec![0u8; 40000];
+    getrandom(&mut input).expect("getrandom");
+
+    for len in LENS {
+        let input = &input[..len];
+        assert_eq!(hash_reader(input, 1).unwrap(), xxh64(input, 1));
+        for max_read in MAX_READS {
+            assert_eq!(hash_reader(ChunkedReader::new(input, max_read), 0).unwrap(), xxh64(input, 0));
+        }
+    }
+
+    assert!(hash_reader(FailingReader, 0).is_err());
+
+    let path = std::env::temp_dir().join(format!("reader-xxh64-{}", std::process::id()));
+    std::fs::write(&path, &input).unwrap();
+    assert_eq!(hash_file(&path, 1).unwrap(), xxh64(&input, 1));
+    std::fs::remove_file(&path).unwrap();
+    assert!(hash_file(&path, 1).is_err());
+}
+
+#[cfg(feature = "xxh32")]
+#[test]
+fn reader_xxh32() {
+    use xxhash_rust::xxh32::{hash_file, hash_reader, xxh32};
+
+    let mut input = vec![0u8; 40000];
+    getrandom(&mut input).expect("getrandom");
+
+    for len in LENS {
+        let input = &input[..len];
+        assert_eq!(hash_reader(input, 1).unwrap(), xxh32(input, 1));
+        for max_read in MAX_READS {
+            assert_eq!(hash_reader(ChunkedReader::new(input, max_read), 0).unwrap(), xxh32(input, 0));
+        }
+    }
+
+    assert!(hash_reader(FailingReader, 0).is_err());
+
+    let path = std::env::temp_dir().join(format!("reader-xxh32-{}", std::process::id()));
+    std::fs::write(&path, &input).unwrap();
+    assert_eq!(hash_file(&path, 1).unwrap(), xxh32(&input, 1));
+    std::fs::remove_file(&path).unwrap();
+    assert!(hash_file(&path, 1).is_err());
+}
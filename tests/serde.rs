@@ -0,0 +1,128 @@
+#![cfg(feature = "serde")]
+
+use getrandom::fill as getrandom;
+
+//Hashes input with round-trip of serialized state at every split point
+fn assert_resume<T, F, R>(name: &str, new: F, update: fn(&mut T, &[u8]), digest: fn(&T) -> R)
+    where T: serde::Serialize + serde::de::DeserializeOwned, F: Fn() -> T, R: PartialEq + core::fmt::Debug
+{
+    let mut input = [0u8; 1100];
+    getrandom(&mut input).expect("getrandom");
+
+    for len in [0, 1, 15, 16, 31, 32, 63, 64, 65, 200, 240, 241, 256, 257, 320, 1024, 1100] {
+        let input = &input[..len];
+        let mut hasher = new();
+        update(&mut hasher, input);
+        let expected = digest(&hasher);
+
+        for split in [0, 1, 3, 16, 33, 64, 255, 256, 300, 513, len] {
+            if split > len {
+                continue;
+            }
+            println!("{}: input(len={}) split={}", name, len, split);
+
+            let mut hasher = new();
+            update(&mut hasher, &input[..split]);
+
+            let json = serde_json::to_string(&hasher).expect("serialize json");
+            let mut restored: T = serde_json::from_str(&json).expect("deserialize json");
+            update(&mut restored, &input[split..]);
+            assert_eq!(digest(&restored), expected);
+
+            let bytes = bincode::serialize(&hasher).expect("serialize bincode");
+            let mut restored: T = bincode::deserialize(&bytes).expect("deserialize bincode");
+            assert_eq!(digest(&restored), digest(&hasher));
+            update(&mut restored, &input[split..]);
+            assert_eq!(digest(&restored), expected);
+        }
+    }
+}
+
+#[cfg(feature = "xxh32")]
+#[test]
+fn serde_xxh32() {
+    use xxhash_rust::xxh32::Xxh32;
+
+    assert_resume("xxh32", || Xxh32::new(1), Xxh32::update, Xxh32::digest);
+
+    let json = serde_json::to_string(&Xxh32::new(0)).unwrap().replace("\"version\":1", "\"version\":2");
+    assert!(serde_json::from_str::<Xxh32>(&json).is_err());
+
+    //Inconsistent states are rejected the same way as by `from_bytes`
+    let mut hasher = Xxh32::new(0);
+    hasher.update(&[1; 30]);
+    let json = serde_json::to_string(&hasher).unwrap();
+    assert!(serde_json::from_str::<Xxh32>(&json).is_ok());
+    for (from, to) in [("\"total_len\":30", "\"total_len\":33"), ("\"is_large_len\":true", "\"is_large_len\":false")] {
+        let corrupted = json.replace(from, to);
+        assert_ne!(corrupted, json);
+        assert!(serde_json::from_str::<Xxh32>(&corrupted).is_err());
+    }
+}
+
+#[cfg(feature = "xxh64")]
+#[test]
+fn serde_xxh64() {
+    use xxhash_rust::xxh64::Xxh64;
+
+    assert_resume("xxh64", || Xxh64::new(1), Xxh64::update, Xxh64::digest);
+
+    let json = serde_json::to_string(&Xxh64::new(0)).unwrap().replace("\"version\":1", "\"version\":2");
+    assert!(serde_json::from_str::<Xxh64>(&json).is_err());
+
+    //Inconsistent states are rejected the same way as by `from_bytes`
+    let mut hasher = Xxh64::new(0);
+    hasher.update(&[1; 100]);
+    let json = serde_json::to_string(&hasher).unwrap();
+    assert!(serde_json::from_str::<Xxh64>(&json).is_ok());
+    let corrupted = json.replace("\"total_len\":100", "\"total_len\":101");
+    assert_ne!(corrupted, json);
+    assert!(serde_json::from_str::<Xxh64>(&corrupted).is_err());
+}
+
+#[cfg(feature = "xxh3")]
+#[test]
+fn serde_xxh3() {
+    use xxhash_rust::xxh3::{Xxh3, Xxh3Default};
+
+    let mut secret = [0u8; 192];
+    getrandom(&mut secret).expect("getrandom");
+
+    assert_resume("xxh3_default", Xxh3Default::new, Xxh3Default::update, Xxh3Default::digest);
+    assert_resume("xxh3_default_128", Xxh3Default::new, Xxh3Default::update, Xxh3Default::digest128);
+    assert_resume("xxh3_seed", || Xxh3::with_seed(1), Xxh3::update, Xxh3::digest128);
+    assert_resume("xxh3_secret", || Xxh3::with_secret(secret), Xxh3::update, Xxh3::digest);
+    assert_resume("xxh3_secret_and_seed", || Xxh3::with_secret_and_seed(secret, 1), Xxh3::update, Xxh3::digest128);
+
+    let mut hasher = Xxh3Default::new();
+    hasher.update(&secret);
+    let json = serde_json::to_string(&hasher).unwrap();
+    let corrupted = json.replace("\"last_stripe\":[]", "\"last_stripe\":[0]");
+    assert_ne!(corrupted, json);
+    assert!(serde_json::from_str::<Xxh3Default>(&corrupted).is_err());
+    let corrupted = json.replace("\"version\":1", "\"version\":2");
+    assert!(serde_json::from_str::<Xxh3Default>(&corrupted).is_err());
+}
+
+#[test]
+fn serde_canonical() {
+    use xxhash_rust::canonical::{Xxh32Canonical, Xxh64Canonical, Xxh128Canonical, Xxh128Hash};
+
+    let canonical = Xxh32Canonical::from_hash(0x0123_4567);
+    assert_eq!(serde_json::to_string(&canonical).unwrap(), "\"01234567\"");
+    assert_eq!(serde_json::from_str::<Xxh32Canonical>("\"01234567\"").unwrap(), canonical);
+    assert_eq!(bincode::deserialize::<Xxh32Canonical>(&bincode::serialize(&canonical).unwrap()).unwrap(), canonical);
+
+    let canonical = Xxh64Canonical::from_hash(0x0123_4567_89ab_cdef);
+    assert_eq!(serde_json::from_str::<Xxh64Canonical>(&serde_json::to_string(&canonical).unwrap()).unwrap(), canonical);
+    assert_eq!(bincode::deserialize::<Xxh64Canonical>(&bincode::serialize(&canonical).unwrap()).unwrap(), canonical);
+    assert!(serde_json::from_str::<Xxh64Canonical>("\"01234567\"").is_err());
+
+    let canonical = Xxh128Canonical::from_hash(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+    assert_eq!(serde_json::from_str::<Xxh128Canonical>(&serde_json::to_string(&canonical).unwrap()).unwrap(), canonical);
+    assert_eq!(bincode::deserialize::<Xxh128Canonical>(&bincode::serialize(&canonical).unwrap()).unwrap(), canonical);
+
+    let hash = Xxh128Hash::new(1, 2);
+    assert_eq!(serde_json::to_string(&hash).unwrap(), "{\"low64\":1,\"high64\":2}");
+    assert_eq!(serde_json::from_str::<Xxh128Hash>("{\"low64\":1,\"high64\":2}").unwrap(), hash);
+}
@@ -15,3 +15,9 @@ fn size_xxh64() {
 fn size_xxh3() {
     assert_eq!(core::mem::size_of::<xxhash_rust::xxh3::Xxh3>(), 576);
 }
+
+#[cfg(feature = "xxh3")]
+#[test]
+fn size_xxh3_fast() {
+    assert_eq!(core::mem::size_of::<xxhash_rust::xxh3::Xxh3Fast>(), 384);
+}
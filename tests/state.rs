@@ -0,0 +1,206 @@
+#![cfg(any(feature = "xxh32", feature = "xxh64", feature = "xxh3"))]
+
+use getrandom::fill as getrandom;
+use xxhash_rust::state::StateError;
+
+//Hashes input with round-trip of exported state at every split point
+fn assert_resume<T, B, F, R>(name: &str, new: F, update: fn(&mut T, &[u8]), digest: fn(&T) -> R, to_bytes: fn(&T) -> B, from_bytes: fn(&B) -> Result<T, StateError>)
+    where B: AsMut<[u8]> + Clone, F: Fn() -> T, R: PartialEq + core::fmt::Debug
+{
+    let mut input = [0u8; 1100];
+    getrandom(&mut input).expect("getrandom");
+
+    for len in [0, 1, 15, 16, 31, 32, 63, 64, 65, 200, 240, 241, 256, 257, 320, 1024, 1100] {
+        let input = &input[..len];
+        let mut hasher = new();
+        update(&mut hasher, input);
+        let expected = digest(&hasher);
+
+        for split in [0, 1, 3, 16, 33, 64, 255, 256, 300, 513, len] {
+            if split > len {
+                continue;
+            }
+            println!("{}: input(len={}) split={}", name, len, split);
+
+            let mut hasher = new();
+            update(&mut hasher, &input[..split]);
+
+            let bytes = to_bytes(&hasher);
+            let mut restored = from_bytes(&bytes).expect("valid state");
+            assert_eq!(digest(&restored), digest(&hasher));
+            update(&mut restored, &input[split..]);
+            assert_eq!(digest(&restored), expected);
+
+            let mut corrupted = bytes.clone();
+            corrupted.as_mut()[0] ^= 1;
+            assert_eq!(from_bytes(&corrupted).err(), Some(StateError::InvalidTag));
+
+            let mut corrupted = bytes.clone();
+            corrupted.as_mut()[3] = 0;
+            assert_eq!(from_bytes(&corrupted).err(), Some(StateError::UnsupportedVersion));
+
+            for idx in 4..bytes.clone().as_mut().len() {
+                let mut corrupted = bytes.clone();
+                corrupted.as_mut()[idx] ^= 0x10;
+                assert_eq!(from_bytes(&corrupted).err(), Some(StateError::ChecksumMismatch));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "xxh32")]
+#[test]
+fn state_xxh32() {
+    use xxhash_rust::xxh32::Xxh32;
+
+    assert_resume("xxh32", || Xxh32::new(1), Xxh32::update, Xxh32::digest, Xxh32::to_bytes, Xxh32::from_bytes);
+}
+
+#[cfg(feature = "xxh64")]
+#[test]
+fn state_xxh64() {
+    use xxhash_rust::xxh64::Xxh64;
+
+    assert_resume("xxh64", || Xxh64::new(1), Xxh64::update, Xxh64::digest, Xxh64::to_bytes, Xxh64::from_bytes);
+}
+
+#[cfg(feature = "xxh3")]
+#[test]
+fn state_xxh3() {
+    use xxhash_rust::xxh3::Xxh3Default;
+
+    assert_resume("xxh3", Xxh3Default::new, Xxh3Default::update, Xxh3Default::digest, Xxh3Default::to_bytes, Xxh3Default::from_bytes);
+    assert_resume("xxh3_128", Xxh3Default::new, Xxh3Default::update, Xxh3Default::digest128, Xxh3Default::to_bytes, Xxh3Default::from_bytes);
+
+    //Export is deterministic
+    let mut hasher = Xxh3Default::new();
+    hasher.update(&[1; 1000]);
+    hasher.update(&[2; 10]);
+    assert_eq!(hasher.to_bytes()[..], Xxh3Default::from_bytes(&hasher.to_bytes()).unwrap().to_bytes()[..]);
+}
+
+#[cfg(feature = "xxh3")]
+#[test]
+fn state_xxh3_inconsistent() {
+    use xxhash_rust::xxh3::{xxh3_64, Xxh3Default};
+
+    const CHECKSUM_OFFSET: usize = Xxh3Default::STATE_SIZE - 8;
+
+    fn craft(hasher: &Xxh3Default, modify: fn(&mut [u8])) -> Result<Xxh3Default, StateError> {
+        let mut bytes = hasher.to_bytes();
+        modify(&mut bytes);
+        let checksum = xxh3_64(&bytes[..CHECKSUM_OFFSET]);
+        bytes[CHECKSUM_OFFSET..].copy_from_slice(&checksum.to_le_bytes());
+        Xxh3Default::from_bytes(&bytes)
+    }
+
+    let mut hasher = Xxh3Default::new();
+    hasher.update(&[1; 10]);
+    assert!(craft(&hasher, |_| ()).is_ok());
+
+    //Buffered size doesn't match total length of short input
+    let result = craft(&hasher, |bytes| {
+        bytes[16..18].copy_from_slice(&5u16.to_le_bytes());
+        bytes[18 + 64 + 5..18 + 64 + 10].fill(0);
+    });
+    assert_eq!(result.err(), Some(StateError::InvalidState));
+
+    //Total length doesn't match buffered input
+    let result = craft(&hasher, |bytes| bytes[4..12].copy_from_slice(&12u64.to_le_bytes()));
+    assert_eq!(result.err(), Some(StateError::InvalidState));
+
+    hasher.update(&[2; 1000]);
+    assert!(craft(&hasher, |_| ()).is_ok());
+
+    //Total length of long input is not consistent with consumed input
+    let result = craft(&hasher, |bytes| bytes[4..12].copy_from_slice(&1011u64.to_le_bytes()));
+    assert_eq!(result.err(), Some(StateError::InvalidState));
+
+    //Number of accumulated stripes is not consistent with consumed input
+    let result = craft(&hasher, |bytes| bytes[12..16].copy_from_slice(&1u32.to_le_bytes()));
+    assert_eq!(result.err(), Some(StateError::InvalidState));
+}
+
+#[cfg(feature = "xxh32")]
+#[test]
+fn state_xxh32_inconsistent() {
+    use xxhash_rust::xxh32::{xxh32, Xxh32};
+
+    const CHECKSUM_OFFSET: usize = Xxh32::STATE_SIZE - 4;
+
+    fn craft(hasher: &Xxh32, modify: fn(&mut [u8])) -> Result<Xxh32, StateError> {
+        let mut bytes = hasher.to_bytes();
+        modify(&mut bytes);
+        let checksum = xxh32(&bytes[..CHECKSUM_OFFSET], 0);
+        bytes[CHECKSUM_OFFSET..].copy_from_slice(&checksum.to_le_bytes());
+        Xxh32::from_bytes(&bytes)
+    }
+
+    let mut hasher = Xxh32::new(0);
+    hasher.update(&[1; 10]);
+    assert!(craft(&hasher, |_| ()).is_ok());
+
+    //Total length doesn't match buffered input
+    let result = craft(&hasher, |bytes| bytes[4..8].copy_from_slice(&12u32.to_le_bytes()));
+    assert_eq!(result.err(), Some(StateError::InvalidState));
+
+    //Buffered size doesn't match total length
+    let result = craft(&hasher, |bytes| {
+        bytes[9] = 5;
+        bytes[26 + 5..26 + 10].fill(0);
+    });
+    assert_eq!(result.err(), Some(StateError::InvalidState));
+
+    hasher.update(&[2; 20]);
+    assert!(craft(&hasher, |_| ()).is_ok());
+
+    //Large input flag is not set for large input
+    let result = craft(&hasher, |bytes| bytes[8] = 0);
+    assert_eq!(result.err(), Some(StateError::InvalidState));
+
+    //Total length of large input is not consistent with buffered size
+    let result = craft(&hasher, |bytes| bytes[4..8].copy_from_slice(&33u32.to_le_bytes()));
+    assert_eq!(result.err(), Some(StateError::InvalidState));
+
+    //Total length wraps around, while large input flag remains set
+    let result = craft(&hasher, |bytes| bytes[4..8].copy_from_slice(&14u32.to_le_bytes()));
+    assert!(result.is_ok());
+}
+
+#[cfg(feature = "xxh64")]
+#[test]
+fn state_xxh64_inconsistent() {
+    use xxhash_rust::xxh64::{xxh64, Xxh64};
+
+    const CHECKSUM_OFFSET: usize = Xxh64::STATE_SIZE - 8;
+
+    fn craft(hasher: &Xxh64, modify: fn(&mut [u8])) -> Result<Xxh64, StateError> {
+        let mut bytes = hasher.to_bytes();
+        modify(&mut bytes);
+        let checksum = xxh64(&bytes[..CHECKSUM_OFFSET], 0);
+        bytes[CHECKSUM_OFFSET..].copy_from_slice(&checksum.to_le_bytes());
+        Xxh64::from_bytes(&bytes)
+    }
+
+    let mut hasher = Xxh64::new(0);
+    hasher.update(&[1; 10]);
+    assert!(craft(&hasher, |_| ()).is_ok());
+
+    //Total length doesn't match buffered input
+    let result = craft(&hasher, |bytes| bytes[4..12].copy_from_slice(&12u64.to_le_bytes()));
+    assert_eq!(result.err(), Some(StateError::InvalidState));
+
+    //Buffered size doesn't match total length
+    let result = craft(&hasher, |bytes| {
+        bytes[12] = 5;
+        bytes[45 + 5..45 + 10].fill(0);
+    });
+    assert_eq!(result.err(), Some(StateError::InvalidState));
+
+    hasher.update(&[2; 100]);
+    assert!(craft(&hasher, |_| ()).is_ok());
+
+    //Total length of large input is not consistent with buffered size
+    let result = craft(&hasher, |bytes| bytes[4..12].copy_from_slice(&111u64.to_le_bytes()));
+    assert_eq!(result.err(), Some(StateError::InvalidState));
+}
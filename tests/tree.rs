@@ -0,0 +1,49 @@
+#![cfg(all(feature = "xxh3", feature = "std"))]
+
+use getrandom::fill as getrandom;
+use xxhash_rust::xxh3::tree::{hash_parallel, hash_parallel_with_threads, hash_sequential};
+use xxhash_rust::xxh3::{xxh3_128, xxh3_128_with_seed};
+
+#[test]
+fn tree_matches_definition() {
+    let mut input = vec![0u8; 10000];
+    getrandom(&mut input).expect("getrandom");
+
+    for (len, chunk_size) in [(0, 1), (1, 1), (100, 1000), (1000, 1000), (1001, 1000), (10000, 1024), (10000, 3)] {
+        let input = &input[..len];
+        let mut leaves = Vec::new();
+        for chunk in input.chunks(chunk_size) {
+            leaves.extend_from_slice(&xxh3_128(chunk).to_be_bytes());
+        }
+        assert_eq!(hash_sequential(input, chunk_size), xxh3_128_with_seed(&leaves, len as u64));
+    }
+}
+
+#[test]
+fn tree_parallel_matches_sequential() {
+    let mut input = vec![0u8; 100_003];
+    getrandom(&mut input).expect("getrandom");
+
+    for len in [0, 1, 4096, 4097, 65536, 100_003] {
+        let input = &input[..len];
+        for chunk_size in [1, 1000, 4096, usize::MAX] {
+            if chunk_size == 1 && len > 4097 {
+                continue;
+            }
+            let expected = hash_sequential(input, chunk_size);
+            assert_eq!(hash_parallel(input, chunk_size), expected);
+            for threads in [0, 1, 2, 3, 7, 64] {
+                assert_eq!(hash_parallel_with_threads(input, chunk_size, threads), expected, "len={} chunk_size={} threads={}", len, chunk_size, threads);
+            }
+        }
+    }
+
+    assert_ne!(hash_sequential(&input, 1000), hash_sequential(&input, 1024));
+    assert_ne!(hash_sequential(&input[..1], 1), hash_sequential(&input[..0], 1));
+}
+
+#[test]
+#[should_panic]
+fn tree_zero_chunk_size() {
+    hash_parallel(b"input", 0);
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+use xxhash_c_sys as sys;
+
+const XXHSUM: &str = env!("CARGO_BIN_EXE_xxhsum");
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("xxhsum-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+fn run(dir: &PathBuf, args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(XXHSUM).args(args)
+                                        .current_dir(dir)
+                                        .stdin(Stdio::piped())
+                                        .stdout(Stdio::piped())
+                                        .stderr(Stdio::piped())
+                                        .spawn()
+                                        .expect("run xxhsum");
+    child.stdin.take().unwrap().write_all(stdin).expect("write stdin");
+    child.wait_with_output().expect("wait xxhsum")
+}
+
+#[test]
+fn xxhsum_hash() {
+    let dir = temp_dir("hash");
+    let input = b"xxhsum test input";
+    std::fs::write(dir.join("input"), input).unwrap();
+
+    let (xxh32, xxh64, xxh3_64, xxh3_128) = unsafe {
+        let xxh128 = sys::XXH3_128bits(input.as_ptr() as _, input.len());
+        (
+            sys::XXH32(input.as_ptr() as _, input.len(), 0),
+            sys::XXH64(input.as_ptr() as _, input.len(), 0),
+            sys::XXH3_64bits(input.as_ptr() as _, input.len()),
+            (xxh128.high64 as u128) << 64 | xxh128.low64 as u128,
+        )
+    };
+
+    let output = run(&dir, &["input"], b"");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), format!("{:016x}  input\n", xxh64));
+
+    let output = run(&dir, &["-H0", "input", "-"], input);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), format!("{0:08x}  input\n{0:08x}  stdin\n", xxh32));
+
+    let output = run(&dir, &["-H2", "input"], b"");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), format!("{:032x}  input\n", xxh3_128));
+
+    let output = run(&dir, &["-H3", "input"], b"");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), format!("XXH3_{:016x}  input\n", xxh3_64));
+
+    let output = run(&dir, &["-H3", "--tag", "input"], b"");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), format!("XXH3 (input) = {:016x}\n", xxh3_64));
+
+    let output = run(&dir, &["-H2", "--tag"], input);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), format!("XXH128 (stdin) = {:032x}\n", xxh3_128));
+
+    let output = run(&dir, &["missing"], b"");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn xxhsum_check() {
+    let dir = temp_dir("check");
+    std::fs::write(dir.join("first"), b"first").unwrap();
+    std::fs::write(dir.join("second"), b"second").unwrap();
+
+    let mut checksums = Vec::new();
+    for (flag, tag) in [("-H0", false), ("-H1", true), ("-H2", false), ("-H3", false), ("-H3", true)] {
+        let mut args = vec![flag, "first", "second"];
+        if tag {
+            args.push("--tag");
+        }
+        let output = run(&dir, &args, b"");
+        assert!(output.status.success());
+        checksums.extend_from_slice(&output.stdout);
+    }
+    std::fs::write(dir.join("sums"), &checksums).unwrap();
+
+    let output = run(&dir, &["-c", "sums"], b"");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "first: OK\nsecond: OK\n".repeat(5));
+
+    let output = run(&dir, &["-c", "-q"], &checksums);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+
+    std::fs::write(dir.join("second"), b"changed").unwrap();
+    checksums.extend_from_slice(b"improper line\n");
+    let output = run(&dir, &["-c", "--quiet", "-"], &checksums);
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), format!("{}1 line is improperly formatted\n5 computed checksums did NOT match\n", "second: FAILED\n".repeat(5)));
+
+    let output = run(&dir, &["-c", "--status", "-"], &checksums);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let output = run(&dir, &["-c", "-"], b"improper line\n");
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "stdin: no properly formatted xxHash checksum lines found\n");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn xxhsum_non_utf8_file_name() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = temp_dir("non-utf8");
+    let file_name = OsStr::from_bytes(b"invalid\xff");
+    let input = b"xxhsum test input";
+    std::fs::write(dir.join(file_name), input).unwrap();
+
+    let xxh64 = unsafe {
+        sys::XXH64(input.as_ptr() as _, input.len(), 0)
+    };
+
+    let mut child = Command::new(XXHSUM).arg(file_name)
+                                        .current_dir(&dir)
+                                        .output()
+                                        .expect("run xxhsum");
+    assert!(child.status.success());
+    let mut expected = format!("{:016x}  ", xxh64).into_bytes();
+    expected.extend_from_slice(b"invalid\xff\n");
+    assert_eq!(child.stdout, expected);
+
+    std::fs::write(dir.join("sums"), &child.stdout).unwrap();
+    child = run(&dir, &["-c", "sums"], b"");
+    assert!(child.status.success());
+    assert_eq!(child.stdout, b"invalid\xff: OK\n");
+
+    std::fs::remove_dir_all(dir).unwrap();
+}